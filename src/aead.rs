@@ -0,0 +1,249 @@
+/*!
+    Common interface for the authenticated encryption modes
+
+    Every AEAD mode implements `Aead`, so code written against the trait can
+    move between GCM, CCM, EAX, OCB, SIV and GCM-SIV by changing one type
+    parameter. `from_key` picks the usual parameters for each mode: a 16-byte
+    tag and the nonce length reported by `nonce_len`. Modes with other
+    parameters are built with their own constructors and still implement the
+    trait.
+*/
+
+use crate::ccm::Ccm;
+use crate::cipher::{BlockCipher, KeyInit};
+use crate::eax::Eax;
+use crate::error::{AesError, Result};
+use crate::gcm::Gcm;
+use crate::gcm_siv::GcmSiv;
+use crate::ocb::Ocb;
+use crate::siv::Siv;
+
+/**
+    Authenticated encryption with associated data
+**/
+pub trait Aead {
+    /**
+        Set up the mode from a raw key with its default parameters
+    **/
+    fn from_key(key: &[u8]) -> Result<Self> where Self: Sized;
+
+    /**
+        Nonce length in bytes callers should use
+    **/
+    fn nonce_len(&self) -> usize;
+
+    /**
+        Tag length in bytes
+    **/
+    fn tag_len(&self) -> usize;
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>>;
+
+    /**
+        Decrypt buf in place, no plaintext is left in buf if the tag does not match
+    **/
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()>;
+
+    /**
+        Encrypt buf in place and append the tag
+    **/
+    fn seal_in_place(&self, nonce: &[u8], aad: &[u8], buf: &mut Vec<u8>) -> Result<()> {
+        let tag = self.seal_in_place_detached(nonce, aad, buf)?;
+        buf.extend_from_slice(&tag);
+
+        Ok(())
+    }
+
+    /**
+        Check the trailing tag and decrypt buf in place, leaving only the plaintext
+    **/
+    fn open_in_place(&self, nonce: &[u8], aad: &[u8], buf: &mut Vec<u8>) -> Result<()> {
+        if buf.len() < self.tag_len() {
+            return Err(AesError::InvalidCiphertextLength(buf.len()));
+        }
+
+        let body_len = buf.len() - self.tag_len();
+        let (body, tag) = buf.split_at_mut(body_len);
+        self.open_in_place_detached(nonce, aad, body, tag)?;
+        buf.truncate(body_len);
+
+        Ok(())
+    }
+
+    /**
+        Encrypt plaintext and return the ciphertext with its tag
+    **/
+    fn seal(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        self.seal_in_place(nonce, aad, &mut out)?;
+
+        Ok(out)
+    }
+
+    /**
+        Check the tag and return the plaintext
+    **/
+    fn open(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = ciphertext.to_vec();
+        self.open_in_place(nonce, aad, &mut out)?;
+
+        Ok(out)
+    }
+}
+
+impl<C: BlockCipher + KeyInit> Aead for Gcm<C> {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Ok(Gcm::new(C::from_key(key)?))
+    }
+
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len()
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        self.encrypt_in_place_detached(nonce, aad, buf)
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(nonce, aad, buf, tag)
+    }
+}
+
+impl<C: BlockCipher + KeyInit> Aead for Ccm<C> {
+    // the RFC 5116 AEAD_AES_*_CCM parameters
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Ccm::new(C::from_key(key)?, 12, 16)
+    }
+
+    fn nonce_len(&self) -> usize {
+        self.nonce_len()
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len()
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        self.encrypt_in_place_detached(nonce, aad, buf)
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(nonce, aad, buf, tag)
+    }
+}
+
+impl<C: BlockCipher + KeyInit> Aead for Eax<C> {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Eax::new(C::from_key(key)?, 16)
+    }
+
+    fn nonce_len(&self) -> usize {
+        16
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len()
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        self.encrypt_in_place_detached(nonce, aad, buf)
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(nonce, aad, buf, tag)
+    }
+}
+
+impl<C: BlockCipher + KeyInit> Aead for Ocb<C> {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Ocb::new(C::from_key(key)?, 16)
+    }
+
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn tag_len(&self) -> usize {
+        self.tag_len()
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        self.encrypt_in_place_detached(nonce, aad, buf)
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(nonce, aad, buf, tag)
+    }
+}
+
+// the associated data and the nonce are the two S2V components, and the
+// synthetic IV goes in front of the ciphertext as in RFC 5297
+impl Aead for Siv {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Siv::new(key)
+    }
+
+    fn nonce_len(&self) -> usize {
+        16
+    }
+
+    fn tag_len(&self) -> usize {
+        16
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        Ok(self.encrypt_in_place_detached(&[aad, nonce], buf)?.to_vec())
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(&[aad, nonce], buf, tag)
+    }
+
+    fn seal_in_place(&self, nonce: &[u8], aad: &[u8], buf: &mut Vec<u8>) -> Result<()> {
+        let v = self.encrypt_in_place_detached(&[aad, nonce], buf)?;
+        buf.splice(0..0, v);
+
+        Ok(())
+    }
+
+    fn open_in_place(&self, nonce: &[u8], aad: &[u8], buf: &mut Vec<u8>) -> Result<()> {
+        if buf.len() < 16 {
+            return Err(AesError::InvalidCiphertextLength(buf.len()));
+        }
+
+        let (v, body) = buf.split_at_mut(16);
+        self.decrypt_in_place_detached(&[aad, nonce], body, v)?;
+        buf.drain(..16);
+
+        Ok(())
+    }
+}
+
+impl Aead for GcmSiv {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        GcmSiv::new(key)
+    }
+
+    fn nonce_len(&self) -> usize {
+        12
+    }
+
+    fn tag_len(&self) -> usize {
+        16
+    }
+
+    fn seal_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        Ok(self.encrypt_in_place_detached(nonce, aad, buf)?.to_vec())
+    }
+
+    fn open_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.decrypt_in_place_detached(nonce, aad, buf, tag)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::sbox;
+
+/**
+    Multiply by x (i.e. 2) in GF(2^8) modulo the AES polynomial
+**/
+pub(crate) fn gf_double(a: u8) -> u8 {
+    let h = (a >> 7) & 1;
+    let mut b = a << 1;
+    b ^= h * 0x1b;
+    b
+}
+
+/**
+    Multiply by x in GF(2^128) modulo x^128 + x^7 + x^2 + x + 1, big-endian
+**/
+pub(crate) fn gf128_double(a: [u8; 16]) -> [u8; 16] {
+    let v = u128::from_be_bytes(a);
+    let h = v >> 127;
+    ((v << 1) ^ (h * 0x87)).to_be_bytes()
+}
+
+/**
+    Multiply a by b in GF(2^8)
+**/
+pub(crate) fn gf_mult(a: u8, b: u8) -> u8 {
+    if b == 1 {
+        return a;
+    }
+
+    let c = b % 2;
+    gf_mult(gf_double(a), (b-c) / 2) ^ (c * a)
+}
+
+/**
+    A 32-bit word, one column of the state
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Word {
+    pub bytes: [u8; 4]
+}
+
+impl std::ops::BitXorAssign for Word {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for i in 0..4 {
+            self.bytes[i] ^= rhs.bytes[i];
+        }
+    }
+}
+
+impl std::ops::BitXor for Word {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        let mut result = self.copy();
+        result ^= rhs;
+        result
+    }
+}
+
+impl Word {
+    pub fn copy(&self) -> Self {
+        Word { bytes: self.bytes }
+    }
+}
+
+/**
+    Word operations
+**/
+impl Word {
+    pub fn rot_word(&mut self) {
+        self.bytes.rotate_left(1);
+    }
+
+    pub fn sub_word(&mut self) {
+        for i in 0..4 {
+            self.bytes[i] = sbox::SBOX[self.bytes[i] as usize];
+        }
+    }
+
+    pub fn inv_sub_word(&mut self) {
+        for i in 0..4 {
+            self.bytes[i] = sbox::INV_SBOX[self.bytes[i] as usize];
+        }
+    }
+
+    // derived from c implementation on wikipedia
+    pub fn mix_column(&mut self) {
+        let a = self.bytes;
+        self.bytes[0] = gf_double(a[0]) ^ a[3] ^ a[2] ^ gf_double(a[1]) ^ a[1];
+        self.bytes[1] = gf_double(a[1]) ^ a[0] ^ a[3] ^ gf_double(a[2]) ^ a[2];
+        self.bytes[2] = gf_double(a[2]) ^ a[1] ^ a[0] ^ gf_double(a[3]) ^ a[3];
+        self.bytes[3] = gf_double(a[3]) ^ a[2] ^ a[1] ^ gf_double(a[0]) ^ a[0];
+    }
+
+    // derived from https://github.com/boppreh/aes (pure magic)
+    pub fn inv_mix_column(&mut self) {
+        let u = gf_mult(self.bytes[0] ^ self.bytes[2], 4);
+        let v = gf_mult(self.bytes[1] ^ self.bytes[3], 4);
+
+        self.bytes[0] ^= u;
+        self.bytes[1] ^= v;
+        self.bytes[2] ^= u;
+        self.bytes[3] ^= v;
+
+        self.mix_column();
+    }
+}
+
+/**
+    A 128-bit block, the cipher state
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block {
+    // 1D array of 4x4 words
+    pub words: [Word; 4]
+}
+
+impl std::ops::BitXor for Block {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        let mut result = self.copy();
+        result ^= rhs;
+        result
+    }
+}
+
+impl std::ops::BitXorAssign for Block {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for i in 0..4 {
+            self.words[i] ^= rhs.words[i];
+        }
+    }
+}
+
+impl Block {
+    pub fn new(bytes: [u8; 16]) -> Self {
+        let mut words: [Word; 4] = [Word { bytes: [0; 4] }; 4];
+
+        for (i, byte) in bytes.iter().enumerate() {
+            words[i / 4].bytes[i % 4] = *byte;
+        }
+
+        Block { words }
+    }
+
+    pub fn copy(&self) -> Self {
+        Block { words: self.words }
+    }
+
+    pub fn as_bytes(&self) -> [u8; 16] {
+        let mut bytes: [u8; 16] = [0; 16];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = self.words[i / 4].bytes[i % 4];
+        }
+
+        bytes
+    }
+}
+
+/**
+    Round transformations
+**/
+impl Block {
+    pub fn sub_bytes(&mut self) {
+        for w in self.words.iter_mut() {
+            w.sub_word();
+        }
+    }
+
+    pub fn inv_sub_bytes(&mut self) {
+        for w in self.words.iter_mut() {
+            w.inv_sub_word();
+        }
+    }
+
+    // row r is rotated left by r columns
+    pub fn shift_rows(&mut self) {
+        let s = self.words;
+        for c in 0..4 {
+            for r in 1..4 {
+                self.words[c].bytes[r] = s[(c + r) % 4].bytes[r];
+            }
+        }
+    }
+
+    pub fn inv_shift_rows(&mut self) {
+        let s = self.words;
+        for (c, w) in s.iter().enumerate() {
+            for r in 1..4 {
+                self.words[(c + r) % 4].bytes[r] = w.bytes[r];
+            }
+        }
+    }
+
+    pub fn mix_columns(&mut self) {
+        for w in self.words.iter_mut() {
+            w.mix_column();
+        }
+    }
+
+    pub fn inv_mix_columns(&mut self) {
+        for w in self.words.iter_mut() {
+            w.inv_mix_column();
+        }
+    }
+
+    pub fn add_round_key(&mut self, key: Block) {
+        for i in 0..4 {
+            self.words[i] ^= key.words[i];
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::block::Block;
+use crate::cipher::{Aes, BlockCipher};
+use crate::error::{AesError, Result};
+use crate::padding::Padding;
+
+/**
+    Encrypt bytes in CBC mode with PKCS#7 padding
+**/
+pub fn encrypt(bytes: &[u8], key: &[u8], iv: [u8; 16]) -> Result<Vec<u8>> {
+    encrypt_padded(bytes, key, iv, Padding::Pkcs7)
+}
+
+/**
+    Decrypt bytes in CBC mode with PKCS#7 padding
+**/
+pub fn decrypt(bytes: &[u8], key: &[u8], iv: [u8; 16]) -> Result<Vec<u8>> {
+    decrypt_padded(bytes, key, iv, Padding::Pkcs7)
+}
+
+/**
+    Encrypt bytes in CBC mode with the given padding scheme
+**/
+pub fn encrypt_padded(bytes: &[u8], key: &[u8], iv: [u8; 16], padding: Padding) -> Result<Vec<u8>> {
+    let aes = Aes::new(key)?;
+    let padded = padding.pad(bytes)?;
+    let mut cipher: Vec<u8> = Vec::with_capacity(padded.len());
+
+    let mut chain = Block::new(iv);
+
+    for chunk in padded.chunks_exact(16) {
+        let block = Block::new(chunk.try_into().unwrap());
+        chain = Block::new(aes.encrypt_block(&(block ^ chain).as_bytes()));
+        cipher.extend_from_slice(&chain.as_bytes());
+    }
+
+    Ok(cipher)
+}
+
+/**
+    Decrypt bytes in CBC mode with the given padding scheme
+**/
+pub fn decrypt_padded(bytes: &[u8], key: &[u8], iv: [u8; 16], padding: Padding) -> Result<Vec<u8>> {
+    let aes = Aes::new(key)?;
+
+    if !bytes.len().is_multiple_of(16) || (bytes.is_empty() && padding != Padding::NoPadding) {
+        return Err(AesError::InvalidCiphertextLength(bytes.len()));
+    }
+
+    let mut plain: Vec<u8> = Vec::with_capacity(bytes.len());
+
+    let mut chain = Block::new(iv);
+
+    for chunk in bytes.chunks_exact(16) {
+        let block = Block::new(chunk.try_into().unwrap());
+        plain.extend_from_slice(&(Block::new(aes.decrypt_block(&block.as_bytes())) ^ chain).as_bytes());
+        chain = block;
+    }
+
+    //remove padding
+    let length = padding.unpad(&plain)?.len();
+    plain.truncate(length);
+
+    Ok(plain)
+}
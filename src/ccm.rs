@@ -0,0 +1,207 @@
+/*!
+    Counter with CBC-MAC (SP 800-38C, RFC 3610)
+
+    The nonce is 7 to 13 bytes; the remaining 15 - n bytes of the counter
+    block hold the message length, which bounds the message size. Tags are
+    an even number of bytes from 4 to 16.
+*/
+
+use crate::cipher::BlockCipher;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    AES-CCM with a fixed key, nonce length and tag length
+**/
+#[derive(Clone, Debug)]
+pub struct Ccm<C: BlockCipher> {
+    cipher: C,
+    nonce_len: usize,
+    tag_len: usize,
+}
+
+impl<C: BlockCipher> Ccm<C> {
+    pub fn new(cipher: C, nonce_len: usize, tag_len: usize) -> Result<Self> {
+        if !(7..=13).contains(&nonce_len) {
+            return Err(AesError::InvalidNonceLength(nonce_len));
+        }
+
+        if !(4..=16).contains(&tag_len) || !tag_len.is_multiple_of(2) {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        Ok(Ccm { cipher, nonce_len, tag_len })
+    }
+
+    pub fn nonce_len(&self) -> usize {
+        self.nonce_len
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /**
+        Size in bytes of the length field, L in RFC 3610
+    **/
+    fn length_size(&self) -> usize {
+        15 - self.nonce_len
+    }
+
+    fn check_lengths(&self, nonce: &[u8], text_len: usize) -> Result<()> {
+        if nonce.len() != self.nonce_len {
+            return Err(AesError::InvalidNonceLength(nonce.len()));
+        }
+
+        // the message length has to fit in L bytes
+        let bits = 8 * self.length_size() as u32;
+        if bits < 64 && (text_len as u64) >> bits != 0 {
+            return Err(AesError::InvalidPlaintextLength(text_len));
+        }
+
+        Ok(())
+    }
+
+    /**
+        Counter block A_i
+    **/
+    fn counter_block(&self, nonce: &[u8], i: u64) -> [u8; 16] {
+        let l = self.length_size();
+
+        let mut block = [0u8; 16];
+        block[0] = (l - 1) as u8;
+        block[1..1 + self.nonce_len].copy_from_slice(nonce);
+
+        let counter = i.to_be_bytes();
+        let width = l.min(8);
+        block[16 - width..].copy_from_slice(&counter[8 - width..]);
+
+        block
+    }
+
+    /**
+        CBC-MAC over B_0, the encoded associated data and the plaintext
+    **/
+    fn mac(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> [u8; 16] {
+        let l = self.length_size();
+
+        // B_0: flags, nonce and message length
+        let mut b0 = [0u8; 16];
+        let adata = if aad.is_empty() { 0 } else { 0x40 };
+        b0[0] = adata | (((self.tag_len - 2) / 2) as u8) << 3 | (l - 1) as u8;
+        b0[1..1 + self.nonce_len].copy_from_slice(nonce);
+
+        let length = (plaintext.len() as u64).to_be_bytes();
+        let width = l.min(8);
+        b0[16 - width..].copy_from_slice(&length[8 - width..]);
+
+        let mut x = self.cipher.encrypt_block(&b0);
+
+        // associated data with its length prefix
+        if !aad.is_empty() {
+            let mut encoded: Vec<u8> = Vec::with_capacity(aad.len() + 10);
+            let len = aad.len() as u64;
+            if len < 0xff00 {
+                encoded.extend_from_slice(&(len as u16).to_be_bytes());
+            } else if len <= u32::MAX as u64 {
+                encoded.extend_from_slice(&[0xff, 0xfe]);
+                encoded.extend_from_slice(&(len as u32).to_be_bytes());
+            } else {
+                encoded.extend_from_slice(&[0xff, 0xff]);
+                encoded.extend_from_slice(&len.to_be_bytes());
+            }
+            encoded.extend_from_slice(aad);
+
+            x = self.cbc_mac_padded(x, &encoded);
+        }
+
+        self.cbc_mac_padded(x, plaintext)
+    }
+
+    fn cbc_mac_padded(&self, mut x: [u8; 16], bytes: &[u8]) -> [u8; 16] {
+        for chunk in bytes.chunks(16) {
+            for (a, b) in x.iter_mut().zip(chunk) {
+                *a ^= b;
+            }
+            x = self.cipher.encrypt_block(&x);
+        }
+
+        x
+    }
+
+    /**
+        CTR encryption with A_1, A_2, ...
+    **/
+    fn ctr(&self, nonce: &[u8], data: &mut [u8]) {
+        for (i, chunk) in data.chunks_mut(16).enumerate() {
+            let keystream = self.cipher.encrypt_block(&self.counter_block(nonce, i as u64 + 1));
+            for (byte, k) in chunk.iter_mut().zip(keystream) {
+                *byte ^= k;
+            }
+        }
+    }
+
+    /**
+        Tag T encrypted with A_0
+    **/
+    fn tag(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let t = self.mac(nonce, aad, plaintext);
+        let s0 = self.cipher.encrypt_block(&self.counter_block(nonce, 0));
+
+        t.iter().zip(s0).take(self.tag_len).map(|(a, b)| a ^ b).collect()
+    }
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    pub fn encrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        self.check_lengths(nonce, buf.len())?;
+
+        let tag = self.tag(nonce, aad, buf);
+        self.ctr(nonce, buf);
+
+        Ok(tag)
+    }
+
+    /**
+        Decrypt buf in place, it is zeroed again if the tag does not match
+    **/
+    pub fn decrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        self.check_lengths(nonce, buf.len())?;
+
+        // the MAC covers the plaintext, so decrypt first and wipe on failure
+        self.ctr(nonce, buf);
+        if !ct_eq(&self.tag(nonce, aad, buf), tag) {
+            buf.fill(0);
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Encrypt and append the tag
+    **/
+    pub fn encrypt(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+
+        Ok(out)
+    }
+
+    /**
+        Verify the trailing tag and decrypt
+    **/
+    pub fn decrypt(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < self.tag_len {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        let mut out = body.to_vec();
+        self.decrypt_in_place_detached(nonce, aad, &mut out, tag)?;
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,116 @@
+/*!
+    Cipher feedback mode (SP 800-38A section 6.3)
+
+    `Cfb` is a streaming en/decryptor: data can be fed in pieces of any
+    length and a partly used CFB-128 segment carries over to the next call.
+    CFB-1 processes every byte as eight one-bit segments, most significant
+    bit first.
+*/
+
+use std::fmt;
+
+use crate::cipher::BlockCipher;
+
+/**
+    Number of bits fed back per cipher invocation
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Bits1,
+    Bits8,
+    Bits128,
+}
+
+/**
+    Streaming CFB state over a block cipher
+**/
+#[derive(Clone)]
+pub struct Cfb<C: BlockCipher> {
+    cipher: C,
+    segment: Segment,
+    // input block of the next cipher invocation
+    register: [u8; 16],
+    // CFB-128 only: current keystream block and bytes of it used so far
+    keystream: [u8; 16],
+    offset: usize,
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Cfb<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the register and the keystream block are left out
+        f.debug_struct("Cfb")
+            .field("cipher", &self.cipher)
+            .field("segment", &self.segment)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Cfb<C> {
+    pub fn new(cipher: C, iv: [u8; 16], segment: Segment) -> Self {
+        Cfb { cipher, segment, register: iv, keystream: [0; 16], offset: 0 }
+    }
+
+    /**
+        Encrypt data in place, continuing where the last call stopped
+    **/
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            *byte = self.process(*byte, false);
+        }
+    }
+
+    /**
+        Decrypt data in place, continuing where the last call stopped
+    **/
+    pub fn decrypt(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            *byte = self.process(*byte, true);
+        }
+    }
+
+    fn process(&mut self, input: u8, decrypt: bool) -> u8 {
+        match self.segment {
+            Segment::Bits1 => {
+                let mut output = 0u8;
+                for i in (0..8).rev() {
+                    let ks = self.cipher.encrypt_block(&self.register)[0] >> 7;
+                    let bit = (input >> i) & 1;
+                    let out = bit ^ ks;
+                    output |= out << i;
+
+                    // the ciphertext bit is fed back
+                    let feedback = if decrypt { bit } else { out };
+                    shift_in_bit(&mut self.register, feedback);
+                }
+                output
+            }
+            Segment::Bits8 => {
+                let output = input ^ self.cipher.encrypt_block(&self.register)[0];
+
+                self.register.rotate_left(1);
+                self.register[15] = if decrypt { input } else { output };
+                output
+            }
+            Segment::Bits128 => {
+                if self.offset == 0 {
+                    self.keystream = self.cipher.encrypt_block(&self.register);
+                }
+
+                let output = input ^ self.keystream[self.offset];
+
+                // the register fills up with this segment's ciphertext
+                self.register[self.offset] = if decrypt { input } else { output };
+                self.offset = (self.offset + 1) % 16;
+                output
+            }
+        }
+    }
+}
+
+/**
+    Shift the register left by one bit and append bit
+**/
+fn shift_in_bit(register: &mut [u8; 16], bit: u8) {
+    let value = (u128::from_be_bytes(*register) << 1) | bit as u128;
+    *register = value.to_be_bytes();
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+
+use crate::block::Block;
+use crate::error::{AesError, Result};
+use crate::key::{determine_key_length, expand_key};
+
+/**
+    Fail unless the schedule holds the n_rounds + 1 round keys
+**/
+fn check_schedule(n_rounds: u8, w: &[Block]) -> Result<()> {
+    if w.len() <= n_rounds as usize {
+        return Err(AesError::InvalidKeyLength(16 * w.len()));
+    }
+
+    Ok(())
+}
+
+/**
+    Encrypt a single block with an expanded key schedule
+**/
+pub fn cipher(inblock: Block, n_rounds: u8, w: &[Block]) -> Result<Block> {
+    check_schedule(n_rounds, w)?;
+
+    Ok(encrypt_state(inblock, n_rounds, w))
+}
+
+/**
+    Decrypt a single block with an expanded key schedule
+**/
+pub fn inv_cipher(inblock: Block, n_rounds: u8, w: &[Block]) -> Result<Block> {
+    check_schedule(n_rounds, w)?;
+
+    Ok(decrypt_state(inblock, n_rounds, w))
+}
+
+fn encrypt_state(inblock: Block, n_rounds: u8, w: &[Block]) -> Block {
+    let mut state = inblock;
+
+    state.add_round_key(w[0]);
+
+    for i in 1..n_rounds {
+        state.sub_bytes();
+        state.shift_rows();
+        state.mix_columns();
+        state.add_round_key(w[i as usize]);
+    }
+
+    state.sub_bytes();
+    state.shift_rows();
+    state.add_round_key(w[n_rounds as usize]);
+
+    state
+}
+
+fn decrypt_state(inblock: Block, n_rounds: u8, w: &[Block]) -> Block {
+    let mut state = inblock;
+
+    state.add_round_key(w[n_rounds as usize]);
+
+    for i in (1..n_rounds).rev() {
+        state.inv_shift_rows();
+        state.inv_sub_bytes();
+        state.add_round_key(w[i as usize]);
+        state.inv_mix_columns();
+    }
+
+    state.inv_shift_rows();
+    state.inv_sub_bytes();
+    state.add_round_key(w[0]);
+
+    state
+}
+
+/**
+    Encrypt a single block with a raw key
+**/
+pub fn aes(inblock: Block, key: &[u8]) -> Result<Block> {
+    let (nk, nr) = determine_key_length(key)?;
+
+    let w = expand_key(key, nk, nr);
+
+    Ok(encrypt_state(inblock, nr, &w))
+}
+
+/**
+    Decrypt a single block with a raw key
+**/
+pub fn inv_aes(inblock: Block, key: &[u8]) -> Result<Block> {
+    let (nk, nr) = determine_key_length(key)?;
+
+    let w = expand_key(key, nk, nr);
+
+    Ok(decrypt_state(inblock, nr, &w))
+}
+
+/**
+    A block cipher keyed once and applied to any number of blocks
+**/
+pub trait BlockCipher {
+    fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16];
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> [u8; 16];
+}
+
+impl<C: BlockCipher + ?Sized> BlockCipher for &C {
+    fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        (**self).encrypt_block(block)
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        (**self).decrypt_block(block)
+    }
+}
+
+/**
+    A block cipher that can be keyed from a byte slice
+**/
+pub trait KeyInit: Sized {
+    fn from_key(key: &[u8]) -> Result<Self>;
+}
+
+macro_rules! fixed_aes {
+    ($name:ident, $key_len:expr, $nk:expr, $nr:expr) => {
+        /**
+            AES with a precomputed key schedule
+        **/
+        #[derive(Clone)]
+        pub struct $name {
+            round_keys: [Block; $nr + 1]
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // round key 0 is the key itself
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        impl $name {
+            pub fn new(key: &[u8; $key_len]) -> Self {
+                let w = expand_key(key, $nk, $nr);
+
+                let mut round_keys = [Block::new([0; 16]); $nr + 1];
+                round_keys.copy_from_slice(&w);
+
+                $name { round_keys }
+            }
+        }
+
+        impl KeyInit for $name {
+            fn from_key(key: &[u8]) -> Result<Self> {
+                let key = key.try_into().map_err(|_| AesError::InvalidKeyLength(key.len()))?;
+
+                Ok($name::new(key))
+            }
+        }
+
+        impl BlockCipher for $name {
+            fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+                encrypt_state(Block::new(*block), $nr, &self.round_keys).as_bytes()
+            }
+
+            fn decrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+                decrypt_state(Block::new(*block), $nr, &self.round_keys).as_bytes()
+            }
+        }
+    };
+}
+
+fixed_aes!(Aes128, 16, 4, 10);
+fixed_aes!(Aes192, 24, 6, 12);
+fixed_aes!(Aes256, 32, 8, 14);
+
+/**
+    AES with a key size chosen at runtime
+**/
+#[derive(Clone)]
+pub struct Aes {
+    n_rounds: u8,
+    round_keys: Vec<Block>
+}
+
+impl fmt::Debug for Aes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // round key 0 is the key itself
+        f.debug_struct("Aes").field("n_rounds", &self.n_rounds).finish_non_exhaustive()
+    }
+}
+
+impl Aes {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let (nk, nr) = determine_key_length(key)?;
+
+        Ok(Aes { n_rounds: nr, round_keys: expand_key(key, nk, nr) })
+    }
+}
+
+impl KeyInit for Aes {
+    fn from_key(key: &[u8]) -> Result<Self> {
+        Aes::new(key)
+    }
+}
+
+impl BlockCipher for Aes {
+    fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        encrypt_state(Block::new(*block), self.n_rounds, &self.round_keys).as_bytes()
+    }
+
+    fn decrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        decrypt_state(Block::new(*block), self.n_rounds, &self.round_keys).as_bytes()
+    }
+}
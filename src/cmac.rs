@@ -0,0 +1,131 @@
+/*!
+    Cipher-based MAC (SP 800-38B, RFC 4493) and AES-CMAC-PRF-128 (RFC 4615)
+
+    `Cmac` is streaming: feed the message through `update` in pieces of any
+    size and `finalize` or `verify` at the end.
+*/
+
+use std::fmt;
+
+use crate::block::gf128_double;
+use crate::cipher::{Aes128, BlockCipher};
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    Streaming CMAC over a block cipher
+**/
+#[derive(Clone)]
+pub struct Cmac<C: BlockCipher> {
+    cipher: C,
+    k1: [u8; 16],
+    k2: [u8; 16],
+    // chaining value over all complete blocks but the last
+    state: [u8; 16],
+    // last, possibly partial, block
+    buffer: [u8; 16],
+    buffered: usize,
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Cmac<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the subkeys and the chaining value are left out
+        f.debug_struct("Cmac")
+            .field("cipher", &self.cipher)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Cmac<C> {
+    pub fn new(cipher: C) -> Self {
+        // subkeys from the encryption of the zero block
+        let l = cipher.encrypt_block(&[0; 16]);
+        let k1 = gf128_double(l);
+        let k2 = gf128_double(k1);
+
+        Cmac { cipher, k1, k2, state: [0; 16], buffer: [0; 16], buffered: 0 }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for byte in data {
+            // a full buffer is only processed once we know it is not the last block
+            if self.buffered == 16 {
+                for (s, b) in self.state.iter_mut().zip(self.buffer) {
+                    *s ^= b;
+                }
+                self.state = self.cipher.encrypt_block(&self.state);
+                self.buffered = 0;
+            }
+
+            self.buffer[self.buffered] = *byte;
+            self.buffered += 1;
+        }
+    }
+
+    /**
+        Full 128-bit tag
+    **/
+    pub fn finalize(&self) -> [u8; 16] {
+        let mut last = [0u8; 16];
+        last[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+
+        let subkey = if self.buffered == 16 {
+            self.k1
+        } else {
+            last[self.buffered] = 0x80;
+            self.k2
+        };
+
+        for ((l, k), s) in last.iter_mut().zip(subkey).zip(self.state) {
+            *l ^= k ^ s;
+        }
+
+        self.cipher.encrypt_block(&last)
+    }
+
+    /**
+        Tag truncated to 8..=16 bytes, the minimum SP 800-38B recommends
+    **/
+    pub fn finalize_truncated(&self, tag_len: usize) -> Result<Vec<u8>> {
+        if !(8..=16).contains(&tag_len) {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        Ok(self.finalize()[..tag_len].to_vec())
+    }
+
+    /**
+        Check a full or truncated tag in constant time
+    **/
+    pub fn verify(&self, tag: &[u8]) -> Result<()> {
+        let expected = self.finalize_truncated(tag.len())?;
+
+        if !ct_eq(&expected, tag) {
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+}
+
+/**
+    CMAC of a whole message
+**/
+pub fn cmac<C: BlockCipher>(cipher: C, message: &[u8]) -> [u8; 16] {
+    let mut mac = Cmac::new(cipher);
+    mac.update(message);
+    mac.finalize()
+}
+
+/**
+    AES-CMAC-PRF-128 with a key of any length (RFC 4615)
+**/
+pub fn prf_128(key: &[u8], message: &[u8]) -> [u8; 16] {
+    // keys other than 128 bits are first compressed with a zero-key CMAC
+    let key: [u8; 16] = match key.try_into() {
+        Ok(key) => key,
+        Err(_) => cmac(Aes128::new(&[0; 16]), key),
+    };
+
+    cmac(Aes128::new(&key), message)
+}
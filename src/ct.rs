@@ -0,0 +1,15 @@
+/**
+    Compare two byte strings without branching on their contents
+**/
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+
+    std::hint::black_box(diff) == 0
+}
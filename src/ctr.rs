@@ -0,0 +1,145 @@
+/*!
+    Counter mode
+
+    The keystream is the encryption of successive counter blocks. The
+    `CounterLayout` decides how many trailing bytes of the initial block form
+    the counter; the leading bytes are a fixed nonce. Running the counter past
+    its maximum is an error rather than a silent wrap.
+*/
+
+use std::fmt;
+
+use crate::cipher::BlockCipher;
+use crate::error::{AesError, Result};
+
+/**
+    Split of the 128-bit counter block into nonce and counter
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterLayout {
+    // 96-bit nonce followed by a 32-bit big-endian counter
+    Nonce96Counter32,
+    // 64-bit nonce followed by a 64-bit big-endian counter
+    Nonce64Counter64,
+    // the whole block is a 128-bit big-endian counter
+    Counter128,
+}
+
+impl CounterLayout {
+    fn counter_bits(&self) -> u32 {
+        match self {
+            CounterLayout::Nonce96Counter32 => 32,
+            CounterLayout::Nonce64Counter64 => 64,
+            CounterLayout::Counter128 => 128,
+        }
+    }
+
+    fn mask(&self) -> u128 {
+        u128::MAX >> (128 - self.counter_bits())
+    }
+}
+
+/**
+    Seekable CTR keystream over a block cipher
+**/
+#[derive(Clone)]
+pub struct Ctr<C: BlockCipher> {
+    cipher: C,
+    layout: CounterLayout,
+    initial: u128,
+    // byte offset into the keystream
+    position: u64,
+    // index and contents of the last generated keystream block
+    cached: Option<(u64, [u8; 16])>,
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Ctr<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the cached keystream block is left out
+        f.debug_struct("Ctr")
+            .field("cipher", &self.cipher)
+            .field("layout", &self.layout)
+            .field("initial", &self.initial)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    /**
+        Start a keystream at the given initial counter block
+    **/
+    pub fn new(cipher: C, initial_block: [u8; 16], layout: CounterLayout) -> Self {
+        Ctr {
+            cipher,
+            layout,
+            initial: u128::from_be_bytes(initial_block),
+            position: 0,
+            cached: None,
+        }
+    }
+
+    /**
+        Byte offset of the next keystream byte
+    **/
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /**
+        Move to an arbitrary byte offset in the keystream
+    **/
+    pub fn seek(&mut self, offset: u64) {
+        self.position = offset;
+    }
+
+    /**
+        Counter block for the given block index, if the counter does not overflow
+    **/
+    fn counter_block(&self, index: u64) -> Option<[u8; 16]> {
+        let mask = self.layout.mask();
+        let counter = (self.initial & mask).checked_add(index as u128)?;
+
+        if counter > mask {
+            return None;
+        }
+
+        Some(((self.initial & !mask) | counter).to_be_bytes())
+    }
+
+    fn keystream_block(&mut self, index: u64) -> [u8; 16] {
+        if let Some((cached_index, block)) = self.cached {
+            if cached_index == index {
+                return block;
+            }
+        }
+
+        // range was checked by the caller
+        let block = self.cipher.encrypt_block(&self.counter_block(index).unwrap());
+        self.cached = Some((index, block));
+        block
+    }
+
+    /**
+        XOR the keystream into data, encryption and decryption are the same
+    **/
+    pub fn apply_keystream(&mut self, data: &mut [u8]) -> Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        // make sure the whole range is covered before touching data
+        let end = self.position.checked_add(data.len() as u64).ok_or(AesError::CounterOverflow)?;
+        if self.counter_block((end - 1) / 16).is_none() {
+            return Err(AesError::CounterOverflow);
+        }
+
+        for byte in data.iter_mut() {
+            let block = self.keystream_block(self.position / 16);
+            *byte ^= block[(self.position % 16) as usize];
+            self.position += 1;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+/*!
+    CTR_DRBG deterministic random bit generator (SP 800-90A Rev. 1)
+
+    The AES variant with a full-block counter, with or without the block
+    cipher derivation function. `instantiate`, `reseed` and `generate` are
+    the functions of the standard and take their entropy from the caller;
+    `from_os_random` and `fill` get it from the operating system, and
+    `next_iv` hands out IVs for `encrypt`.
+*/
+
+use std::fmt;
+
+use crate::cipher::{Aes, BlockCipher};
+use crate::error::{AesError, Result};
+use crate::rand::os_random;
+
+/**
+    Largest request for one generate call, 2^19 bits
+**/
+pub const MAX_REQUEST: usize = 1 << 16;
+
+/**
+    Largest number of generate calls between reseeds, 2^48
+**/
+pub const MAX_RESEED_INTERVAL: u64 = 1 << 48;
+
+/**
+    Block cipher derivation function, returns len bytes
+**/
+fn block_cipher_df(key_len: usize, input: &[u8], len: usize) -> Vec<u8> {
+    // S = L || N || input || 0x80, zero padded to whole blocks
+    let mut s = (input.len() as u32).to_be_bytes().to_vec();
+    s.extend_from_slice(&(len as u32).to_be_bytes());
+    s.extend_from_slice(input);
+    s.push(0x80);
+    s.resize(s.len().div_ceil(16) * 16, 0);
+
+    let k: Vec<u8> = (0..key_len as u8).collect();
+    let bcc_cipher = Aes::new(&k).unwrap();
+
+    let mut temp: Vec<u8> = Vec::with_capacity(key_len + 16);
+    let mut i: u32 = 0;
+    while temp.len() < key_len + 16 {
+        // BCC, a CBC-MAC over IV || S where IV is the counter i
+        let mut iv = [0u8; 16];
+        iv[..4].copy_from_slice(&i.to_be_bytes());
+        let mut chain = bcc_cipher.encrypt_block(&iv);
+        for block in s.chunks_exact(16) {
+            for (c, b) in chain.iter_mut().zip(block) {
+                *c ^= b;
+            }
+            chain = bcc_cipher.encrypt_block(&chain);
+        }
+
+        temp.extend_from_slice(&chain);
+        i += 1;
+    }
+
+    let cipher = Aes::new(&temp[..key_len]).unwrap();
+    let mut x: [u8; 16] = temp[key_len..key_len + 16].try_into().unwrap();
+
+    let mut out: Vec<u8> = Vec::with_capacity(len + 16);
+    while out.len() < len {
+        x = cipher.encrypt_block(&x);
+        out.extend_from_slice(&x);
+    }
+
+    out.truncate(len);
+    out
+}
+
+/**
+    AES CTR_DRBG with a 128, 192 or 256-bit key
+**/
+#[derive(Clone)]
+pub struct CtrDrbg {
+    cipher: Aes,
+    key_len: usize,
+    v: [u8; 16],
+    reseed_counter: u64,
+    reseed_interval: u64,
+    derivation_function: bool,
+    prediction_resistance: bool,
+}
+
+impl fmt::Debug for CtrDrbg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // V is left out, it is part of the secret working state
+        f.debug_struct("CtrDrbg")
+            .field("cipher", &self.cipher)
+            .field("key_len", &self.key_len)
+            .field("reseed_counter", &self.reseed_counter)
+            .field("reseed_interval", &self.reseed_interval)
+            .field("derivation_function", &self.derivation_function)
+            .field("prediction_resistance", &self.prediction_resistance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CtrDrbg {
+    /**
+        Instantiate from caller supplied entropy, the nonce is only used by the derivation function
+    **/
+    pub fn instantiate(key_len: usize, derivation_function: bool, entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self> {
+        if !matches!(key_len, 16 | 24 | 32) {
+            return Err(AesError::InvalidKeyLength(key_len));
+        }
+
+        let mut drbg = CtrDrbg {
+            cipher: Aes::new(&vec![0u8; key_len])?,
+            key_len,
+            v: [0; 16],
+            reseed_counter: 1,
+            reseed_interval: MAX_RESEED_INTERVAL,
+            derivation_function,
+            prediction_resistance: false,
+        };
+
+        let seed_material = if derivation_function {
+            // at least the security strength of entropy and half of it as nonce
+            drbg.check_entropy(entropy)?;
+            if nonce.len() < key_len / 2 {
+                return Err(AesError::InvalidSeedLength(nonce.len()));
+            }
+
+            let mut input = entropy.to_vec();
+            input.extend_from_slice(nonce);
+            input.extend_from_slice(personalization);
+            block_cipher_df(key_len, &input, drbg.seed_len())
+        } else {
+            drbg.check_entropy(entropy)?;
+            drbg.xor_padded(entropy, personalization)?
+        };
+
+        drbg.update(&seed_material);
+        Ok(drbg)
+    }
+
+    /**
+        Instantiate with the derivation function from the operating system's random source
+    **/
+    pub fn from_os_random(key_len: usize) -> Result<Self> {
+        let mut entropy = vec![0u8; key_len + 16];
+        let mut nonce = [0u8; 16];
+        os_random(&mut entropy)?;
+        os_random(&mut nonce)?;
+
+        CtrDrbg::instantiate(key_len, true, &entropy, &nonce, &[])
+    }
+
+    /**
+        Require a reseed after this many generate calls, at most 2^48
+    **/
+    pub fn with_reseed_interval(mut self, interval: u64) -> Self {
+        self.reseed_interval = interval.clamp(1, MAX_RESEED_INTERVAL);
+        self
+    }
+
+    /**
+        Have `fill` reseed from the operating system before every request
+    **/
+    pub fn with_prediction_resistance(mut self) -> Self {
+        self.prediction_resistance = true;
+        self
+    }
+
+    /**
+        Length of the seed material, key length plus one block
+    **/
+    fn seed_len(&self) -> usize {
+        self.key_len + 16
+    }
+
+    fn check_entropy(&self, entropy: &[u8]) -> Result<()> {
+        let accepted = if self.derivation_function {
+            entropy.len() >= self.key_len
+        } else {
+            entropy.len() == self.seed_len()
+        };
+
+        if !accepted {
+            return Err(AesError::InvalidSeedLength(entropy.len()));
+        }
+
+        Ok(())
+    }
+
+    /**
+        Seed material without the derivation function, input xor the zero padded extra data
+    **/
+    fn xor_padded(&self, input: &[u8], extra: &[u8]) -> Result<Vec<u8>> {
+        if extra.len() > self.seed_len() {
+            return Err(AesError::InvalidSeedLength(extra.len()));
+        }
+
+        let mut out = input.to_vec();
+        out.resize(self.seed_len(), 0);
+        for (o, e) in out.iter_mut().zip(extra) {
+            *o ^= e;
+        }
+
+        Ok(out)
+    }
+
+    /**
+        Bring additional input to the seed length, empty input stays empty
+    **/
+    fn additional_input(&self, additional: &[u8]) -> Result<Vec<u8>> {
+        if additional.is_empty() {
+            Ok(Vec::new())
+        } else if self.derivation_function {
+            Ok(block_cipher_df(self.key_len, additional, self.seed_len()))
+        } else {
+            self.xor_padded(&[], additional)
+        }
+    }
+
+    fn increment_v(&mut self) {
+        self.v = u128::from_be_bytes(self.v).wrapping_add(1).to_be_bytes();
+    }
+
+    /**
+        CTR_DRBG_Update, provided data shorter than the seed length is zero padded
+    **/
+    fn update(&mut self, provided: &[u8]) {
+        let mut temp: Vec<u8> = Vec::with_capacity(self.seed_len() + 16);
+        while temp.len() < self.seed_len() {
+            self.increment_v();
+            temp.extend_from_slice(&self.cipher.encrypt_block(&self.v));
+        }
+
+        temp.truncate(self.seed_len());
+        for (t, p) in temp.iter_mut().zip(provided) {
+            *t ^= p;
+        }
+
+        self.cipher = Aes::new(&temp[..self.key_len]).unwrap();
+        self.v.copy_from_slice(&temp[self.key_len..]);
+    }
+
+    /**
+        Reseed with fresh entropy and optional additional input
+    **/
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<()> {
+        self.check_entropy(entropy)?;
+
+        let seed_material = if self.derivation_function {
+            let mut input = entropy.to_vec();
+            input.extend_from_slice(additional);
+            block_cipher_df(self.key_len, &input, self.seed_len())
+        } else {
+            self.xor_padded(entropy, additional)?
+        };
+
+        self.update(&seed_material);
+        self.reseed_counter = 1;
+
+        Ok(())
+    }
+
+    /**
+        Fill out with random bytes, fails with `ReseedRequired` once the reseed interval is used up
+    **/
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<()> {
+        if out.len() > MAX_REQUEST {
+            return Err(AesError::InvalidRequestLength(out.len()));
+        }
+
+        if self.reseed_counter > self.reseed_interval {
+            return Err(AesError::ReseedRequired);
+        }
+
+        let additional = self.additional_input(additional)?;
+        if !additional.is_empty() {
+            self.update(&additional);
+        }
+
+        for chunk in out.chunks_mut(16) {
+            self.increment_v();
+            let block = self.cipher.encrypt_block(&self.v);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        self.update(&additional);
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+
+    /**
+        Generate with prediction resistance, reseeding with the entropy and additional input first
+    **/
+    pub fn generate_with_prediction_resistance(&mut self, entropy: &[u8], out: &mut [u8], additional: &[u8]) -> Result<()> {
+        self.reseed(entropy, additional)?;
+        self.generate(out, &[])
+    }
+
+    /**
+        Fill out of any length, reseeding from the operating system when needed
+    **/
+    pub fn fill(&mut self, out: &mut [u8]) -> Result<()> {
+        for chunk in out.chunks_mut(MAX_REQUEST) {
+            if self.prediction_resistance || self.reseed_counter > self.reseed_interval {
+                let mut entropy = vec![0u8; self.seed_len()];
+                os_random(&mut entropy)?;
+                self.reseed(&entropy, &[])?;
+            }
+
+            self.generate(chunk, &[])?;
+        }
+
+        Ok(())
+    }
+
+    /**
+        A fresh random IV for `encrypt` and the other CBC functions
+    **/
+    pub fn next_iv(&mut self) -> Result<[u8; 16]> {
+        let mut iv = [0u8; 16];
+        self.fill(&mut iv)?;
+
+        Ok(iv)
+    }
+}
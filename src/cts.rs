@@ -0,0 +1,127 @@
+/*!
+    CBC with ciphertext stealing (SP 800-38A Addendum)
+
+    The ciphertext is exactly as long as the plaintext, which must be at
+    least one block. The three variants only differ in the order of the
+    last two ciphertext blocks: CS1 keeps CBC order, CS3 (the Kerberos
+    variant of RFC 3962) always swaps them and CS2 swaps them only when the
+    last block is partial.
+*/
+
+use crate::cipher::BlockCipher;
+use crate::error::{AesError, Result};
+
+/**
+    Ordering of the last two ciphertext blocks
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CtsVariant {
+    Cs1,
+    Cs2,
+    Cs3,
+}
+
+impl CtsVariant {
+    fn swaps(&self, partial: bool) -> bool {
+        match self {
+            CtsVariant::Cs1 => false,
+            CtsVariant::Cs2 => partial,
+            CtsVariant::Cs3 => true,
+        }
+    }
+}
+
+fn xor_into(a: &mut [u8; 16], b: &[u8]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+/**
+    Encrypt at least one block of plaintext without padding expansion
+**/
+pub fn encrypt<C: BlockCipher>(cipher: &C, iv: [u8; 16], plaintext: &[u8], variant: CtsVariant) -> Result<Vec<u8>> {
+    if plaintext.len() < 16 {
+        return Err(AesError::InvalidPlaintextLength(plaintext.len()));
+    }
+
+    // plain CBC over the zero padded plaintext
+    let mut out: Vec<u8> = Vec::with_capacity(plaintext.len() + 16);
+    let mut chain = iv;
+    for chunk in plaintext.chunks(16) {
+        xor_into(&mut chain, chunk);
+        chain = cipher.encrypt_block(&chain);
+        out.extend_from_slice(&chain);
+    }
+
+    let n = out.len() / 16;
+    if n == 1 {
+        return Ok(out);
+    }
+
+    // steal: C_{n-1} is cut down to the length of the last plaintext block
+    let tail = plaintext.len() - 16 * (n - 1);
+    let last: [u8; 16] = out[16 * (n - 1)..].try_into().unwrap();
+    let stolen = out[16 * (n - 2)..16 * (n - 2) + tail].to_vec();
+    out.truncate(16 * (n - 2));
+
+    if variant.swaps(tail < 16) {
+        out.extend_from_slice(&last);
+        out.extend_from_slice(&stolen);
+    } else {
+        out.extend_from_slice(&stolen);
+        out.extend_from_slice(&last);
+    }
+
+    Ok(out)
+}
+
+/**
+    Decrypt ciphertext produced by `encrypt` with the same variant
+**/
+pub fn decrypt<C: BlockCipher>(cipher: &C, iv: [u8; 16], ciphertext: &[u8], variant: CtsVariant) -> Result<Vec<u8>> {
+    if ciphertext.len() < 16 {
+        return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+    }
+
+    let n = ciphertext.len().div_ceil(16);
+    let tail = ciphertext.len() - 16 * (n - 1);
+
+    // rebuild the full CBC ciphertext C_1 .. C_n
+    let mut blocks: Vec<[u8; 16]> = ciphertext[..16 * n.saturating_sub(2)]
+        .chunks_exact(16)
+        .map(|c| c.try_into().unwrap())
+        .collect();
+
+    if n > 1 {
+        let rest = &ciphertext[16 * (n - 2)..];
+        let (stolen, last) = if variant.swaps(tail < 16) {
+            (&rest[16..], &rest[..16])
+        } else {
+            (&rest[..tail], &rest[tail..])
+        };
+        let last: [u8; 16] = last.try_into().unwrap();
+
+        // the stolen bytes of C_{n-1} are recovered from D(C_n)
+        let mut previous = cipher.decrypt_block(&last);
+        previous[..tail].copy_from_slice(stolen);
+
+        blocks.push(previous);
+        blocks.push(last);
+    } else {
+        blocks.push(ciphertext.try_into().unwrap());
+    }
+
+    let mut out: Vec<u8> = Vec::with_capacity(16 * n);
+    let mut chain = iv;
+    for block in blocks {
+        let mut plain = cipher.decrypt_block(&block);
+        xor_into(&mut plain, &chain);
+        out.extend_from_slice(&plain);
+        chain = block;
+    }
+
+    // the last block decrypts to the plaintext followed by the zero padding
+    out.truncate(ciphertext.len());
+    Ok(out)
+}
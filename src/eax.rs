@@ -0,0 +1,120 @@
+/*!
+    EAX authenticated encryption (Bellare, Rogaway and Wagner)
+
+    Built from CMAC (OMAC) and CTR: the nonce, the header and the ciphertext
+    are each MACed under a different tweak and the three results are XORed
+    into the tag. Nonces and headers may have any length, tags are 1 to 16
+    bytes.
+*/
+
+use crate::cipher::BlockCipher;
+use crate::cmac::Cmac;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    AES-EAX with a fixed key and tag length
+**/
+#[derive(Clone, Debug)]
+pub struct Eax<C: BlockCipher> {
+    cipher: C,
+    tag_len: usize,
+}
+
+impl<C: BlockCipher> Eax<C> {
+    pub fn new(cipher: C, tag_len: usize) -> Result<Self> {
+        if !(1..=16).contains(&tag_len) {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        Ok(Eax { cipher, tag_len })
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /**
+        OMAC^t, CMAC over the block [t]_n followed by data
+    **/
+    fn omac(&self, t: u8, data: &[u8]) -> [u8; 16] {
+        let mut tweak = [0u8; 16];
+        tweak[15] = t;
+
+        let mut mac = Cmac::new(&self.cipher);
+        mac.update(&tweak);
+        mac.update(data);
+        mac.finalize()
+    }
+
+    /**
+        CTR with the whole block as counter, starting at N'
+    **/
+    fn ctr(&self, n: &[u8; 16], data: &mut [u8]) {
+        let n = u128::from_be_bytes(*n);
+
+        for (i, chunk) in data.chunks_mut(16).enumerate() {
+            let keystream = self.cipher.encrypt_block(&n.wrapping_add(i as u128).to_be_bytes());
+            for (byte, k) in chunk.iter_mut().zip(keystream) {
+                *byte ^= k;
+            }
+        }
+    }
+
+    fn tag(&self, n: &[u8; 16], header: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let h = self.omac(1, header);
+        let c = self.omac(2, ciphertext);
+
+        (0..self.tag_len).map(|i| n[i] ^ h[i] ^ c[i]).collect()
+    }
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    pub fn encrypt_in_place_detached(&self, nonce: &[u8], header: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        let n = self.omac(0, nonce);
+        self.ctr(&n, buf);
+
+        Ok(self.tag(&n, header, buf))
+    }
+
+    /**
+        Check the tag and only then decrypt buf in place
+    **/
+    pub fn decrypt_in_place_detached(&self, nonce: &[u8], header: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        let n = self.omac(0, nonce);
+
+        if !ct_eq(&self.tag(&n, header, buf), tag) {
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        self.ctr(&n, buf);
+        Ok(())
+    }
+
+    /**
+        Encrypt and append the tag
+    **/
+    pub fn encrypt(&self, nonce: &[u8], header: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, header, &mut out)?;
+        out.extend_from_slice(&tag);
+
+        Ok(out)
+    }
+
+    /**
+        Verify the trailing tag and decrypt
+    **/
+    pub fn decrypt(&self, nonce: &[u8], header: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < self.tag_len {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        let mut out = body.to_vec();
+        self.decrypt_in_place_detached(nonce, header, &mut out, tag)?;
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,42 @@
+/*!
+    Electronic codebook mode
+
+    Every block is encrypted independently, so equal plaintext blocks give
+    equal ciphertext blocks. Only use this for interoperability or as a
+    building block; it is deliberately not re-exported from the crate root.
+*/
+
+use crate::cipher::BlockCipher;
+use crate::error::{AesError, Result};
+
+/**
+    Encrypt whole blocks in ECB mode, no padding is applied
+**/
+pub fn encrypt_blocks<C: BlockCipher>(cipher: &C, bytes: &[u8]) -> Result<Vec<u8>> {
+    if !bytes.len().is_multiple_of(16) {
+        return Err(AesError::InvalidPlaintextLength(bytes.len()));
+    }
+
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    for chunk in bytes.chunks_exact(16) {
+        out.extend_from_slice(&cipher.encrypt_block(chunk.try_into().unwrap()));
+    }
+
+    Ok(out)
+}
+
+/**
+    Decrypt whole blocks in ECB mode
+**/
+pub fn decrypt_blocks<C: BlockCipher>(cipher: &C, bytes: &[u8]) -> Result<Vec<u8>> {
+    if !bytes.len().is_multiple_of(16) {
+        return Err(AesError::InvalidCiphertextLength(bytes.len()));
+    }
+
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    for chunk in bytes.chunks_exact(16) {
+        out.extend_from_slice(&cipher.decrypt_block(chunk.try_into().unwrap()));
+    }
+
+    Ok(out)
+}
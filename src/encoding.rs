@@ -0,0 +1,71 @@
+/*!
+    Hex and base64 decoding for keys and IVs given as text
+
+    Both decoders are strict: every character must belong to the encoding,
+    and base64 must be canonical. Padding has to match the length, and the
+    unused low bits of the last character have to be zero, so each byte
+    string has exactly one accepted encoding.
+*/
+
+use crate::error::{AesError, Result};
+
+/**
+    Decode a hex string, upper or lower case, whitespace is not allowed
+**/
+pub fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(AesError::InvalidKeyEncoding);
+    }
+
+    Ok((0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect())
+}
+
+/**
+    Decode standard base64, the trailing padding may be left out but must be right if present
+**/
+pub fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    let body = s.trim_end_matches('=');
+    let pad = s.len() - body.len();
+
+    // a lone character carries too few bits for a byte
+    let expected_pad = match body.len() % 4 {
+        0 => 0,
+        2 => 2,
+        3 => 1,
+        _ => return Err(AesError::InvalidKeyEncoding),
+    };
+    if pad != 0 && pad != expected_pad {
+        return Err(AesError::InvalidKeyEncoding);
+    }
+
+    let mut out = Vec::with_capacity(body.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for c in body.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(AesError::InvalidKeyEncoding),
+        };
+
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+
+    // leftover bits are only padding
+    if acc & ((1 << bits) - 1) != 0 {
+        return Err(AesError::InvalidKeyEncoding);
+    }
+
+    Ok(out)
+}
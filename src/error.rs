@@ -0,0 +1,91 @@
+use std::{fmt, io};
+
+/**
+    Errors returned by the cipher, the modes and the CLI
+**/
+#[derive(Debug)]
+pub enum AesError {
+    // key is not 16, 24 or 32 bytes
+    InvalidKeyLength(usize),
+    // XTS data key and tweak key are the same (SP 800-38E)
+    DuplicateXtsKey,
+    // plaintext is not a whole number of blocks and no padding was requested
+    InvalidPlaintextLength(usize),
+    // ciphertext is not a whole number of blocks
+    InvalidCiphertextLength(usize),
+    // associated data is longer than the mode allows
+    InvalidAssociatedDataLength(usize),
+    BadPadding,
+    // nonce or IV length not accepted by the mode
+    InvalidNonceLength(usize),
+    // tag length not accepted by the mode
+    InvalidTagLength(usize),
+    AuthenticationFailed,
+    // keystream position is past the end of the counter space
+    CounterOverflow,
+    // saved OFB state points past the end of its register
+    InvalidStateOffset(usize),
+    // entropy, nonce, personalization string or additional input length not accepted by the DRBG
+    InvalidSeedLength(usize),
+    // more bytes requested from the DRBG than one generate call may return
+    InvalidRequestLength(usize),
+    // the DRBG has produced its limit of requests since the last reseed
+    ReseedRequired,
+    // PBKDF2 needs at least one iteration
+    InvalidIterationCount(u32),
+    // key or IV text is not valid hex or base64
+    InvalidKeyEncoding,
+    // environment variable named on the command line is not set
+    MissingEnvVar(String),
+    EmptyPassword,
+    // the password and its confirmation differ
+    PasswordMismatch,
+    // input to password decryption does not start with the password header
+    NotPasswordEncrypted,
+    Io(io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, AesError>;
+
+impl fmt::Display for AesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AesError::InvalidKeyLength(len) => write!(f, "invalid key length: {} bytes", len),
+            AesError::DuplicateXtsKey => write!(f, "xts data key and tweak key are identical"),
+            AesError::InvalidPlaintextLength(len) => write!(f, "invalid plaintext length: {} bytes", len),
+            AesError::InvalidCiphertextLength(len) => write!(f, "invalid ciphertext length: {} bytes", len),
+            AesError::InvalidAssociatedDataLength(len) => write!(f, "invalid associated data length: {} bytes", len),
+            AesError::BadPadding => write!(f, "bad padding"),
+            AesError::InvalidNonceLength(len) => write!(f, "invalid nonce length: {} bytes", len),
+            AesError::InvalidTagLength(len) => write!(f, "invalid tag length: {} bytes", len),
+            AesError::AuthenticationFailed => write!(f, "authentication failed"),
+            AesError::CounterOverflow => write!(f, "counter overflow"),
+            AesError::InvalidStateOffset(offset) => write!(f, "invalid keystream offset: {}", offset),
+            AesError::InvalidSeedLength(len) => write!(f, "invalid seed material length: {} bytes", len),
+            AesError::InvalidRequestLength(len) => write!(f, "invalid request length: {} bytes", len),
+            AesError::ReseedRequired => write!(f, "reseed required"),
+            AesError::InvalidIterationCount(count) => write!(f, "invalid iteration count: {}", count),
+            AesError::InvalidKeyEncoding => write!(f, "invalid key encoding"),
+            AesError::MissingEnvVar(name) => write!(f, "environment variable {} is not set", name),
+            AesError::EmptyPassword => write!(f, "empty password"),
+            AesError::PasswordMismatch => write!(f, "passwords do not match"),
+            AesError::NotPasswordEncrypted => write!(f, "input was not encrypted with a password"),
+            AesError::Io(e) => write!(f, "i/o error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AesError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AesError {
+    fn from(e: io::Error) -> Self {
+        AesError::Io(e)
+    }
+}
@@ -0,0 +1,193 @@
+/*!
+    Galois/Counter Mode (SP 800-38D)
+
+    Authenticated encryption with associated data. The ciphertext is
+    followed by the tag; on decryption the tag is checked before any
+    plaintext is produced.
+*/
+
+use std::fmt;
+
+use crate::cipher::BlockCipher;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+use crate::ghash::Ghash;
+
+/**
+    Largest plaintext allowed under one IV, 2^39 - 256 bits
+**/
+const MAX_TEXT: u64 = (1 << 36) - 32;
+
+/**
+    AES-GCM with a fixed key and tag length
+**/
+#[derive(Clone)]
+pub struct Gcm<C: BlockCipher> {
+    cipher: C,
+    h: [u8; 16],
+    tag_len: usize,
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Gcm<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the hash subkey is left out, it is E(K, 0)
+        f.debug_struct("Gcm")
+            .field("cipher", &self.cipher)
+            .field("tag_len", &self.tag_len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Gcm<C> {
+    /**
+        GCM with a full 128-bit tag
+    **/
+    pub fn new(cipher: C) -> Self {
+        let h = cipher.encrypt_block(&[0; 16]);
+
+        Gcm { cipher, h, tag_len: 16 }
+    }
+
+    /**
+        Truncate tags to 12..=16 bytes
+    **/
+    pub fn with_tag_len(mut self, tag_len: usize) -> Result<Self> {
+        if !(12..=16).contains(&tag_len) {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        self.tag_len = tag_len;
+        Ok(self)
+    }
+
+    /**
+        Allow 4 or 8 byte tags, see SP 800-38D Appendix C before using these
+    **/
+    pub fn with_short_tag_len(mut self, tag_len: usize) -> Result<Self> {
+        if tag_len != 4 && tag_len != 8 {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        self.tag_len = tag_len;
+        Ok(self)
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /**
+        Pre-counter block J0 for an IV of any non-zero length
+    **/
+    fn j0(&self, iv: &[u8]) -> Result<[u8; 16]> {
+        if iv.is_empty() {
+            return Err(AesError::InvalidNonceLength(0));
+        }
+
+        if iv.len() == 12 {
+            let mut j0 = [0u8; 16];
+            j0[..12].copy_from_slice(iv);
+            j0[15] = 1;
+            return Ok(j0);
+        }
+
+        let mut ghash = Ghash::new(&self.h);
+        ghash.update_padded(iv);
+
+        let mut lengths = [0u8; 16];
+        lengths[8..].copy_from_slice(&(iv.len() as u64 * 8).to_be_bytes());
+        ghash.update_block(&lengths);
+
+        Ok(ghash.finalize())
+    }
+
+    /**
+        GCTR starting at inc32(J0), the counter wraps modulo 2^32
+    **/
+    fn gctr(&self, j0: &[u8; 16], data: &mut [u8]) {
+        let mut counter = *j0;
+
+        for chunk in data.chunks_mut(16) {
+            let low = u32::from_be_bytes(counter[12..].try_into().unwrap()).wrapping_add(1);
+            counter[12..].copy_from_slice(&low.to_be_bytes());
+
+            let keystream = self.cipher.encrypt_block(&counter);
+            for (byte, k) in chunk.iter_mut().zip(keystream) {
+                *byte ^= k;
+            }
+        }
+    }
+
+    fn tag(&self, j0: &[u8; 16], aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let mut ghash = Ghash::new(&self.h);
+        ghash.update_padded(aad);
+        ghash.update_padded(ciphertext);
+
+        let mut lengths = [0u8; 16];
+        lengths[..8].copy_from_slice(&(aad.len() as u64 * 8).to_be_bytes());
+        lengths[8..].copy_from_slice(&(ciphertext.len() as u64 * 8).to_be_bytes());
+        ghash.update_block(&lengths);
+
+        let mask = self.cipher.encrypt_block(j0);
+        let s = ghash.finalize();
+
+        s.iter().zip(mask).take(self.tag_len).map(|(a, b)| a ^ b).collect()
+    }
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    pub fn encrypt_in_place_detached(&self, iv: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        if buf.len() as u64 > MAX_TEXT {
+            return Err(AesError::InvalidPlaintextLength(buf.len()));
+        }
+
+        let j0 = self.j0(iv)?;
+        self.gctr(&j0, buf);
+
+        Ok(self.tag(&j0, aad, buf))
+    }
+
+    /**
+        Check the tag and only then decrypt buf in place
+    **/
+    pub fn decrypt_in_place_detached(&self, iv: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        if buf.len() as u64 > MAX_TEXT {
+            return Err(AesError::InvalidCiphertextLength(buf.len()));
+        }
+
+        let j0 = self.j0(iv)?;
+        if !ct_eq(&self.tag(&j0, aad, buf), tag) {
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        self.gctr(&j0, buf);
+        Ok(())
+    }
+
+    /**
+        Encrypt and append the tag
+    **/
+    pub fn encrypt(&self, iv: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(iv, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+
+        Ok(out)
+    }
+
+    /**
+        Verify the trailing tag and decrypt
+    **/
+    pub fn decrypt(&self, iv: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < self.tag_len {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        let mut out = body.to_vec();
+        self.decrypt_in_place_detached(iv, aad, &mut out, tag)?;
+
+        Ok(out)
+    }
+}
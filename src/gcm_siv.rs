@@ -0,0 +1,169 @@
+/*!
+    AES-GCM-SIV nonce misuse-resistant AEAD (RFC 8452)
+
+    Per-nonce authentication and encryption keys are derived from the
+    key-generating key, the tag is computed over POLYVAL of the associated
+    data and plaintext, and the tag doubles as the initial CTR block.
+    Repeating a nonce only reveals whether the same message was sent twice.
+*/
+
+use crate::cipher::{Aes, BlockCipher};
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+use crate::polyval::Polyval;
+
+/**
+    Largest plaintext and associated data, 2^36 bytes
+**/
+const MAX_LEN: u64 = 1 << 36;
+
+/**
+    AES-GCM-SIV with a 128 or 256-bit key-generating key
+**/
+#[derive(Clone, Debug)]
+pub struct GcmSiv {
+    key_generating_key: Aes,
+    key_len: usize,
+}
+
+impl GcmSiv {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.len() != 16 && key.len() != 32 {
+            return Err(AesError::InvalidKeyLength(key.len()));
+        }
+
+        Ok(GcmSiv { key_generating_key: Aes::new(key)?, key_len: key.len() })
+    }
+
+    /**
+        Message authentication key and message encryption key for a nonce
+    **/
+    fn derive_keys(&self, nonce: &[u8]) -> ([u8; 16], Aes) {
+        let mut derived: Vec<u8> = Vec::with_capacity(48);
+
+        // the first half of each encrypted counter block is kept
+        for i in 0..(2 + self.key_len / 8) as u32 {
+            let mut block = [0u8; 16];
+            block[..4].copy_from_slice(&i.to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+            derived.extend_from_slice(&self.key_generating_key.encrypt_block(&block)[..8]);
+        }
+
+        let auth_key: [u8; 16] = derived[..16].try_into().unwrap();
+        // 16 or 32 bytes, always a valid AES key
+        let enc_key = Aes::new(&derived[16..]).unwrap();
+
+        (auth_key, enc_key)
+    }
+
+    fn check_lengths(nonce: &[u8], aad: &[u8], text_len: usize) -> Result<()> {
+        if nonce.len() != 12 {
+            return Err(AesError::InvalidNonceLength(nonce.len()));
+        }
+
+        if text_len as u64 > MAX_LEN {
+            return Err(AesError::InvalidPlaintextLength(text_len));
+        }
+
+        if aad.len() as u64 > MAX_LEN {
+            return Err(AesError::InvalidAssociatedDataLength(aad.len()));
+        }
+
+        Ok(())
+    }
+
+    fn tag(auth_key: &[u8; 16], enc_key: &Aes, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> [u8; 16] {
+        let mut polyval = Polyval::new(auth_key);
+        polyval.update_padded(aad);
+        polyval.update_padded(plaintext);
+
+        let mut lengths = [0u8; 16];
+        lengths[..8].copy_from_slice(&(aad.len() as u64 * 8).to_le_bytes());
+        lengths[8..].copy_from_slice(&(plaintext.len() as u64 * 8).to_le_bytes());
+        polyval.update_block(&lengths);
+
+        let mut s = polyval.finalize();
+        for (x, n) in s.iter_mut().zip(nonce) {
+            *x ^= n;
+        }
+        s[15] &= 0x7f;
+
+        enc_key.encrypt_block(&s)
+    }
+
+    /**
+        CTR with a 32-bit little-endian counter in the first four bytes
+    **/
+    fn ctr(enc_key: &Aes, tag: &[u8; 16], data: &mut [u8]) {
+        let mut counter = *tag;
+        counter[15] |= 0x80;
+
+        for chunk in data.chunks_mut(16) {
+            let keystream = enc_key.encrypt_block(&counter);
+            for (byte, k) in chunk.iter_mut().zip(keystream) {
+                *byte ^= k;
+            }
+
+            let low = u32::from_le_bytes(counter[..4].try_into().unwrap()).wrapping_add(1);
+            counter[..4].copy_from_slice(&low.to_le_bytes());
+        }
+    }
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    pub fn encrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<[u8; 16]> {
+        Self::check_lengths(nonce, aad, buf.len())?;
+
+        let (auth_key, enc_key) = self.derive_keys(nonce);
+        let tag = Self::tag(&auth_key, &enc_key, nonce, aad, buf);
+        Self::ctr(&enc_key, &tag, buf);
+
+        Ok(tag)
+    }
+
+    /**
+        Decrypt buf in place, it is zeroed again if the tag does not match
+    **/
+    pub fn decrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        Self::check_lengths(nonce, aad, buf.len())?;
+
+        let tag: [u8; 16] = tag.try_into().map_err(|_| AesError::InvalidTagLength(tag.len()))?;
+
+        let (auth_key, enc_key) = self.derive_keys(nonce);
+        Self::ctr(&enc_key, &tag, buf);
+
+        if !ct_eq(&Self::tag(&auth_key, &enc_key, nonce, aad, buf), &tag) {
+            buf.fill(0);
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Encrypt and append the tag
+    **/
+    pub fn encrypt(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+
+        Ok(out)
+    }
+
+    /**
+        Verify the trailing tag and decrypt
+    **/
+    pub fn decrypt(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < 16 {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - 16);
+        let mut out = body.to_vec();
+        self.decrypt_in_place_detached(nonce, aad, &mut out, tag)?;
+
+        Ok(out)
+    }
+}
@@ -0,0 +1,75 @@
+/*!
+    GHASH, the universal hash of GCM (SP 800-38D section 6.4)
+
+    Blocks are elements of GF(2^128) in GCM's bit-reflected convention,
+    held as big-endian `u128`s.
+*/
+
+use std::fmt;
+
+/**
+    Reduction constant, x^128 = x^7 + x^2 + x + 1 in reflected order
+**/
+pub(crate) const R: u128 = 0xe1 << 120;
+
+/**
+    Multiply two field elements without data-dependent branches
+**/
+pub fn gf128_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0u128;
+    let mut v = y;
+
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & 0u128.wrapping_sub(bit);
+
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & 0u128.wrapping_sub(lsb));
+    }
+
+    z
+}
+
+/**
+    Incremental GHASH under a hash subkey
+**/
+#[derive(Clone)]
+pub struct Ghash {
+    h: u128,
+    y: u128,
+}
+
+impl fmt::Debug for Ghash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the hash subkey and the accumulator are left out
+        f.debug_struct("Ghash").finish_non_exhaustive()
+    }
+}
+
+impl Ghash {
+    pub fn new(h: &[u8; 16]) -> Self {
+        Ghash { h: u128::from_be_bytes(*h), y: 0 }
+    }
+
+    /**
+        Absorb one block
+    **/
+    pub fn update_block(&mut self, block: &[u8; 16]) {
+        self.y = gf128_mul(self.y ^ u128::from_be_bytes(*block), self.h);
+    }
+
+    /**
+        Absorb bytes, zero-padding the last partial block
+    **/
+    pub fn update_padded(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.update_block(&block);
+        }
+    }
+
+    pub fn finalize(&self) -> [u8; 16] {
+        self.y.to_be_bytes()
+    }
+}
@@ -0,0 +1,138 @@
+/*!
+    HCTR2 length-preserving encryption (Crowley, Huckleberry and Biggers)
+
+    A wide-block tweakable cipher built from XCTR and POLYVAL: the whole
+    message is one block of the construction, so changing any bit of the
+    plaintext or the tweak changes every bit of the ciphertext. Messages
+    are at least 16 bytes and the ciphertext is exactly as long as the
+    plaintext, which suits filenames and fixed-size records. There is no
+    tag, so nothing is authenticated.
+*/
+
+use std::fmt;
+
+use crate::cipher::BlockCipher;
+use crate::error::{AesError, Result};
+use crate::polyval::Polyval;
+
+fn xor_into(a: &mut [u8], b: &[u8]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+/**
+    HCTR2 over a block cipher, with the hash key and L precomputed
+**/
+#[derive(Clone)]
+pub struct Hctr2<C: BlockCipher> {
+    cipher: C,
+    h: [u8; 16],
+    l: [u8; 16],
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Hctr2<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the hash key and L are left out, they are encryptions under the key
+        f.debug_struct("Hctr2")
+            .field("cipher", &self.cipher)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Hctr2<C> {
+    pub fn new(cipher: C) -> Self {
+        let h = cipher.encrypt_block(&0u128.to_le_bytes());
+        let l = cipher.encrypt_block(&1u128.to_le_bytes());
+
+        Hctr2 { cipher, h, l }
+    }
+
+    /**
+        POLYVAL over the tweak length, the padded tweak and the padded message
+    **/
+    fn hash(&self, tweak: &[u8], message: &[u8]) -> [u8; 16] {
+        let partial = !message.len().is_multiple_of(16);
+        let length = 16 * tweak.len() as u128 + 2 + partial as u128;
+
+        let mut polyval = Polyval::new(&self.h);
+        polyval.update_block(&length.to_le_bytes());
+        polyval.update_padded(tweak);
+
+        let full = message.len() - message.len() % 16;
+        polyval.update_padded(&message[..full]);
+
+        if partial {
+            // the last block is padded with a single one bit
+            let rest = &message[full..];
+            let mut block = [0u8; 16];
+            block[..rest.len()].copy_from_slice(rest);
+            block[rest.len()] = 1;
+            polyval.update_block(&block);
+        }
+
+        polyval.finalize()
+    }
+
+    /**
+        XCTR, block i of the keystream is E(S xor i) for i = 1, 2, ...
+    **/
+    fn xctr(&self, s: &[u8; 16], data: &mut [u8]) {
+        let s = u128::from_le_bytes(*s);
+
+        for (i, chunk) in data.chunks_mut(16).enumerate() {
+            let keystream = self.cipher.encrypt_block(&(s ^ (i as u128 + 1)).to_le_bytes());
+            xor_into(chunk, &keystream);
+        }
+    }
+
+    /**
+        Encrypt buf in place under a tweak of any length
+    **/
+    pub fn encrypt(&self, tweak: &[u8], buf: &mut [u8]) -> Result<()> {
+        if buf.len() < 16 {
+            return Err(AesError::InvalidPlaintextLength(buf.len()));
+        }
+
+        let (m, n) = buf.split_at_mut(16);
+
+        let mut mm: [u8; 16] = m.try_into().unwrap();
+        xor_into(&mut mm, &self.hash(tweak, n));
+        let uu = self.cipher.encrypt_block(&mm);
+
+        let mut s = mm;
+        xor_into(&mut s, &uu);
+        xor_into(&mut s, &self.l);
+        self.xctr(&s, n);
+
+        m.copy_from_slice(&uu);
+        xor_into(m, &self.hash(tweak, n));
+
+        Ok(())
+    }
+
+    /**
+        Decrypt buf in place under the tweak it was encrypted with
+    **/
+    pub fn decrypt(&self, tweak: &[u8], buf: &mut [u8]) -> Result<()> {
+        if buf.len() < 16 {
+            return Err(AesError::InvalidCiphertextLength(buf.len()));
+        }
+
+        let (u, v) = buf.split_at_mut(16);
+
+        let mut uu: [u8; 16] = u.try_into().unwrap();
+        xor_into(&mut uu, &self.hash(tweak, v));
+        let mm = self.cipher.decrypt_block(&uu);
+
+        let mut s = mm;
+        xor_into(&mut s, &uu);
+        xor_into(&mut s, &self.l);
+        self.xctr(&s, v);
+
+        u.copy_from_slice(&mm);
+        xor_into(u, &self.hash(tweak, v));
+
+        Ok(())
+    }
+}
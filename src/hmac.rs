@@ -0,0 +1,78 @@
+/*!
+    HMAC-SHA256 (RFC 2104, FIPS 198-1)
+
+    The inner and outer hashes are keyed once in `new`, so cloning a keyed
+    `HmacSha256` is cheap; PBKDF2 relies on that.
+*/
+
+use std::fmt;
+
+use crate::ct::ct_eq;
+use crate::sha256::{sha256, Sha256};
+
+/**
+    Block size of SHA-256 in bytes
+**/
+const BLOCK: usize = 64;
+
+/**
+    Streaming HMAC-SHA256
+**/
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl fmt::Debug for HmacSha256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the hash states are left out, they are equivalent to the key
+        f.debug_struct("HmacSha256").finish_non_exhaustive()
+    }
+}
+
+impl HmacSha256 {
+    pub fn new(key: &[u8]) -> Self {
+        // keys longer than a block are hashed first, shorter ones zero padded
+        let mut block = [0u8; BLOCK];
+        if key.len() > BLOCK {
+            block[..32].copy_from_slice(&sha256(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha256::new();
+        inner.update(&block.map(|b| b ^ 0x36));
+
+        let mut outer = Sha256::new();
+        outer.update(&block.map(|b| b ^ 0x5c));
+
+        HmacSha256 { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    /**
+        Compare against an expected tag in constant time
+    **/
+    pub fn verify(self, tag: &[u8]) -> bool {
+        ct_eq(&self.finalize(), tag)
+    }
+}
+
+/**
+    HMAC-SHA256 of a message in one call
+**/
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new(key);
+    mac.update(data);
+    mac.finalize()
+}
@@ -0,0 +1,103 @@
+use crate::block::{Block, Word};
+use crate::error::{AesError, Result};
+
+// Key lengths in words
+// static NK128: u8 = 4;
+// static NK192: u8 = 6;
+// static NK256: u8 = 8;
+
+/**
+    Block size in words
+**/
+pub static NB: u8 = 4;
+
+/**
+    Round constants
+**/
+static R_CON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10,
+                          0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/**
+    Round constant for round j
+**/
+fn rcon(j: u8) -> Word {
+    Word { bytes: [R_CON[j as usize], 0, 0, 0] }
+}
+
+/**
+    Expand a key of nk words into nr + 1 round keys, nk and nr must be an AES pair
+**/
+pub fn key_expansion(key: &[u8], nk: u8, nr: u8) -> Result<Vec<Block>> {
+    if determine_key_length(key)? != (nk, nr) {
+        return Err(AesError::InvalidKeyLength(key.len()));
+    }
+
+    Ok(expand_key(key, nk, nr))
+}
+
+/**
+    Key expansion for a key already checked against nk and nr
+**/
+pub(crate) fn expand_key(key: &[u8], nk: u8, nr: u8) -> Vec<Block> {
+
+    // convert key to words
+    let mut w: Vec<Word> = Vec::new();
+    for i in 0..nk as usize {
+        w.push(
+            Word { bytes: [
+                key[4 * i],
+                key[4 * i + 1],
+                key[4 * i + 2],
+                key[4 * i + 3]
+            ] }
+        );
+    }
+
+    // key expansion
+    for i in nk..NB * (nr + 1) {
+        let mut temp = w[(i - 1) as usize].copy();
+
+        if i % nk == 0 {
+            temp.rot_word();
+            temp.sub_word();
+            temp ^= rcon(i / nk -1);
+        } else if nk > 6 && i % nk == 4 {
+            temp.sub_word();
+        }
+
+        w.push(w[(i - nk) as usize] ^ temp);
+    }
+
+    // convert words to blocks
+    let mut blocks: Vec<Block> = Vec::new();
+    for (i, word) in w.iter().enumerate() {
+        if i % 4 == 0 {
+            blocks.push(Block::new([0; 16]));
+        }
+        blocks[i / 4].words[i % 4] = *word;
+    }
+
+    blocks
+}
+
+/**
+    Key length in words and number of rounds for a key
+**/
+pub fn determine_key_length(key: &[u8]) -> Result<(u8, u8)> {
+    match key.len() {
+        16 => Ok((4, 10)),
+        24 => Ok((6, 12)),
+        32 => Ok((8, 14)),
+        len => Err(AesError::InvalidKeyLength(len)),
+    }
+}
+
+/**
+    Key length in words and number of rounds for each half of a SIV key
+**/
+pub fn determine_siv_key_length(key: &[u8]) -> Result<(u8, u8)> {
+    match key.len() {
+        32 | 48 | 64 => determine_key_length(&key[..key.len() / 2]),
+        len => Err(AesError::InvalidKeyLength(len)),
+    }
+}
@@ -0,0 +1,155 @@
+/*!
+    AES Key Wrap (RFC 3394) and Key Wrap with Padding (RFC 5649)
+
+    Both wrap key material under a key-encryption key (KEK). Unwrapping
+    checks the integrity check value and fails with `AuthenticationFailed`
+    for a wrong KEK or tampered input, without returning any key material.
+*/
+
+use crate::cipher::BlockCipher;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    Default initial value of RFC 3394 section 2.2.3.1
+**/
+const IV: [u8; 8] = [0xa6; 8];
+
+/**
+    Alternative initial value prefix of RFC 5649 section 3
+**/
+const AIV_PREFIX: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+/**
+    Wrapping process W with a given initial value, over n >= 2 semiblocks
+**/
+fn w<C: BlockCipher>(kek: &C, iv: [u8; 8], plaintext: &[u8]) -> Vec<u8> {
+    let n = plaintext.len() / 8;
+    let mut a = iv;
+    let mut r: Vec<[u8; 8]> = plaintext.chunks_exact(8).map(|c| c.try_into().unwrap()).collect();
+
+    for j in 0..6 {
+        for (i, semiblock) in r.iter_mut().enumerate() {
+            let mut block = [0u8; 16];
+            block[..8].copy_from_slice(&a);
+            block[8..].copy_from_slice(semiblock);
+            let b = kek.encrypt_block(&block);
+
+            let t = (n * j + i + 1) as u64;
+            a = (u64::from_be_bytes(b[..8].try_into().unwrap()) ^ t).to_be_bytes();
+            semiblock.copy_from_slice(&b[8..]);
+        }
+    }
+
+    let mut out = a.to_vec();
+    for semiblock in r {
+        out.extend_from_slice(&semiblock);
+    }
+    out
+}
+
+/**
+    Unwrapping process W^-1, returns the recovered initial value and plaintext
+**/
+fn w_inv<C: BlockCipher>(kek: &C, ciphertext: &[u8]) -> ([u8; 8], Vec<u8>) {
+    let n = ciphertext.len() / 8 - 1;
+    let mut a: [u8; 8] = ciphertext[..8].try_into().unwrap();
+    let mut r: Vec<[u8; 8]> = ciphertext[8..].chunks_exact(8).map(|c| c.try_into().unwrap()).collect();
+
+    for j in (0..6).rev() {
+        for i in (0..n).rev() {
+            let t = (n * j + i + 1) as u64;
+
+            let mut block = [0u8; 16];
+            block[..8].copy_from_slice(&(u64::from_be_bytes(a) ^ t).to_be_bytes());
+            block[8..].copy_from_slice(&r[i]);
+            let b = kek.decrypt_block(&block);
+
+            a.copy_from_slice(&b[..8]);
+            r[i].copy_from_slice(&b[8..]);
+        }
+    }
+
+    (a, r.concat())
+}
+
+/**
+    Wrap key material of at least 16 bytes, in multiples of 8 (RFC 3394)
+**/
+pub fn wrap<C: BlockCipher>(kek: &C, key: &[u8]) -> Result<Vec<u8>> {
+    if key.len() < 16 || !key.len().is_multiple_of(8) {
+        return Err(AesError::InvalidPlaintextLength(key.len()));
+    }
+
+    Ok(w(kek, IV, key))
+}
+
+/**
+    Unwrap and verify a key wrapped with `wrap`
+**/
+pub fn unwrap<C: BlockCipher>(kek: &C, wrapped: &[u8]) -> Result<Vec<u8>> {
+    if wrapped.len() < 24 || !wrapped.len().is_multiple_of(8) {
+        return Err(AesError::InvalidCiphertextLength(wrapped.len()));
+    }
+
+    let (a, key) = w_inv(kek, wrapped);
+    if !ct_eq(&a, &IV) {
+        return Err(AesError::AuthenticationFailed);
+    }
+
+    Ok(key)
+}
+
+/**
+    Wrap key material of any non-zero length (RFC 5649)
+**/
+pub fn wrap_pad<C: BlockCipher>(kek: &C, key: &[u8]) -> Result<Vec<u8>> {
+    if key.is_empty() || key.len() > u32::MAX as usize {
+        return Err(AesError::InvalidPlaintextLength(key.len()));
+    }
+
+    let mut aiv = [0u8; 8];
+    aiv[..4].copy_from_slice(&AIV_PREFIX);
+    aiv[4..].copy_from_slice(&(key.len() as u32).to_be_bytes());
+
+    let mut padded = key.to_vec();
+    padded.resize(key.len().div_ceil(8) * 8, 0);
+
+    // a single semiblock is encrypted together with the AIV
+    if padded.len() == 8 {
+        let mut block = [0u8; 16];
+        block[..8].copy_from_slice(&aiv);
+        block[8..].copy_from_slice(&padded);
+        return Ok(kek.encrypt_block(&block).to_vec());
+    }
+
+    Ok(w(kek, aiv, &padded))
+}
+
+/**
+    Unwrap and verify a key wrapped with `wrap_pad`
+**/
+pub fn unwrap_pad<C: BlockCipher>(kek: &C, wrapped: &[u8]) -> Result<Vec<u8>> {
+    if wrapped.len() < 16 || !wrapped.len().is_multiple_of(8) {
+        return Err(AesError::InvalidCiphertextLength(wrapped.len()));
+    }
+
+    let (a, mut key) = if wrapped.len() == 16 {
+        let b = kek.decrypt_block(wrapped.try_into().unwrap());
+        (b[..8].try_into().unwrap(), b[8..].to_vec())
+    } else {
+        w_inv(kek, wrapped)
+    };
+
+    // check the prefix, the message length indicator and the zero padding
+    let mli = u32::from_be_bytes(a[4..].try_into().unwrap()) as usize;
+    let length_ok = mli <= key.len() && mli + 8 > key.len();
+    let padding_ok = length_ok && key[mli..].iter().all(|b| *b == 0);
+
+    if !ct_eq(&a[..4], &AIV_PREFIX) || !length_ok || !padding_ok {
+        return Err(AesError::AuthenticationFailed);
+    }
+
+    key.truncate(mli);
+    Ok(key)
+}
@@ -0,0 +1,69 @@
+/*!
+    AES block cipher, key schedule and modes of operation
+
+    The block cipher works on `Block`s with a key schedule from
+    `key_expansion`. `Aes128`, `Aes192` and `Aes256` hold a precomputed key
+    schedule and implement `BlockCipher`; `encrypt`/`decrypt` run CBC over
+    byte slices, padded with one of the `Padding` schemes. Raw ECB is only
+    reachable through the `ecb` module. The authenticated modes all
+    implement the `Aead` trait.
+*/
+
+mod ct;
+mod sbox;
+
+pub mod aead;
+pub mod block;
+pub mod cbc;
+pub mod ccm;
+pub mod cfb;
+pub mod cipher;
+pub mod cmac;
+pub mod ctr;
+pub mod ctr_drbg;
+pub mod cts;
+pub mod eax;
+pub mod ecb;
+pub mod encoding;
+pub mod error;
+pub mod gcm;
+pub mod gcm_siv;
+pub mod ghash;
+pub mod hctr2;
+pub mod hmac;
+pub mod key;
+pub mod kw;
+pub mod ocb;
+pub mod ofb;
+pub mod padding;
+pub mod pbkdf2;
+pub mod polyval;
+pub mod rand;
+pub mod sha256;
+pub mod siv;
+pub mod xts;
+
+pub use aead::Aead;
+pub use block::{Block, Word};
+pub use cbc::{decrypt, decrypt_padded, encrypt, encrypt_padded};
+pub use ccm::Ccm;
+pub use cfb::{Cfb, Segment};
+pub use cmac::Cmac;
+pub use cipher::{aes, cipher, inv_aes, inv_cipher, Aes, Aes128, Aes192, Aes256, BlockCipher, KeyInit};
+pub use ctr::{CounterLayout, Ctr};
+pub use ctr_drbg::CtrDrbg;
+pub use cts::CtsVariant;
+pub use eax::Eax;
+pub use error::{AesError, Result};
+pub use gcm::Gcm;
+pub use gcm_siv::GcmSiv;
+pub use hctr2::Hctr2;
+pub use hmac::HmacSha256;
+pub use key::{determine_key_length, determine_siv_key_length, key_expansion, NB};
+pub use ocb::Ocb;
+pub use ofb::{Ofb, OfbState, OfbWriter};
+pub use padding::Padding;
+pub use pbkdf2::pbkdf2_hmac_sha256;
+pub use sha256::Sha256;
+pub use siv::Siv;
+pub use xts::Xts;
@@ -1,415 +1,365 @@
-use std::{env, io};
-use std::io::{Read, Write};
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::{Command, ExitCode};
 
-mod sbox;
-
-// Key lengths in words
-// static NK128: u8 = 4;
-// static NK192: u8 = 6;
-// static NK256: u8 = 8;
+use aes::encoding::{decode_base64, decode_hex};
+use aes::rand::os_random;
+use aes::{decrypt_padded, determine_key_length, encrypt_padded, kw, pbkdf2_hmac_sha256, Aes, AesError, Padding};
 
 /**
-    Block size in words
+    Start of the header written in front of password encrypted output
 **/
-static NB: u8 = 4;
+const PASSWORD_MAGIC: &[u8; 8] = b"AESPWD01";
 
 /**
-    Round constants
+    Magic, 32-bit big-endian iteration count and 16-byte salt
 **/
-static R_CON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10,
-                          0x20, 0x40, 0x80, 0x1b, 0x36];
+const PASSWORD_HEADER_LEN: usize = 8 + 4 + 16;
 
-#[derive(Clone, Copy, Debug)]
-struct Word {
-    bytes: [u8; 4]
-}
+/**
+    PBKDF2 iterations for new output unless `--iterations` is given
+**/
+const DEFAULT_ITERATIONS: u32 = 600_000;
 
 /**
-    ???
+    Highest iteration count accepted, the header is read before anything is authenticated
 **/
-fn gf_double(a: u8) -> u8 {
-    let h = (a >> 7) & 1;
-    let mut b = a << 1;
-    b ^= h * 0x1b;
-    b &= 0xff;
-    b
-}
+const MAX_ITERATIONS: u32 = 10_000_000;
 
-fn gf_mult(a: u8, b: u8) -> u8 {
-    if b == 1 {
-        return a;
+/**
+    Exit code for each kind of error, usage errors exit with 2
+**/
+fn exit_code(e: &AesError) -> u8 {
+    match e {
+        AesError::InvalidKeyLength(_) => 3,
+        AesError::DuplicateXtsKey => 3,
+        AesError::InvalidPlaintextLength(_) => 4,
+        AesError::InvalidCiphertextLength(_) => 4,
+        AesError::InvalidAssociatedDataLength(_) => 4,
+        AesError::BadPadding => 5,
+        AesError::AuthenticationFailed => 6,
+        AesError::CounterOverflow => 7,
+        AesError::ReseedRequired => 7,
+        AesError::InvalidStateOffset(_) => 8,
+        AesError::InvalidNonceLength(_) => 8,
+        AesError::InvalidTagLength(_) => 8,
+        AesError::InvalidSeedLength(_) => 8,
+        AesError::InvalidRequestLength(_) => 8,
+        AesError::InvalidIterationCount(_) => 8,
+        AesError::InvalidKeyEncoding => 9,
+        AesError::MissingEnvVar(_) => 2,
+        AesError::EmptyPassword => 10,
+        AesError::PasswordMismatch => 11,
+        AesError::NotPasswordEncrypted => 12,
+        AesError::Io(_) => 74,
     }
+}
 
-    let c = b % 2;
-    gf_mult(gf_double(a), (b-c) / 2) ^ (c * a)
+/**
+    How `--key-file` and `--key-env` contents are read
+**/
+#[derive(Clone, Copy)]
+enum KeyFormat {
+    Raw,
+    Hex,
+    Base64,
 }
 
-impl std::ops::BitXorAssign for Word {
-    fn bitxor_assign(&mut self, rhs: Self) {
-        for i in 0..4 {
-            self.bytes[i] ^= rhs.bytes[i];
+impl KeyFormat {
+    fn from_name(name: &str) -> Option<KeyFormat> {
+        match name {
+            "raw" => Some(KeyFormat::Raw),
+            "hex" => Some(KeyFormat::Hex),
+            "base64" => Some(KeyFormat::Base64),
+            _ => None,
         }
     }
-}
 
-impl std::ops::BitXor for Word {
-    type Output = Self;
+    /**
+        Decode key bytes, surrounding whitespace is ignored for the text formats
+    **/
+    fn decode(&self, bytes: &[u8]) -> aes::Result<Vec<u8>> {
+        let text = || std::str::from_utf8(bytes).map(str::trim).map_err(|_| AesError::InvalidKeyEncoding);
 
-    fn bitxor(self, rhs: Self) -> Self {
-        let mut result = self.copy();
-        result ^= rhs;
-        result
+        match self {
+            KeyFormat::Raw => Ok(bytes.to_vec()),
+            KeyFormat::Hex => decode_hex(text()?),
+            KeyFormat::Base64 => decode_base64(text()?),
+        }
     }
 }
 
-impl Word {
-    fn copy(&self) -> Self {
-        Word { bytes: self.bytes }
-    }
+/**
+    Where the key comes from
+**/
+enum KeySource {
+    // the argument bytes as they are
+    Text(String),
+    Hex(String),
+    Base64(String),
+    File(String, KeyFormat),
+    Env(String, KeyFormat),
+    // PBKDF2 from the password in this environment variable, or prompted for
+    Password(Option<String>),
 }
 
 /**
-    Word operations
+    Parsed command line
 **/
-impl Word {
-    fn rot_word(&mut self) -> () {
-        self.bytes.rotate_left(1);
-    }
-
-    fn sub_word(&mut self) -> () {
-        for i in 0..4 {
-            self.bytes[i] = sbox::SBOX[self.bytes[i] as usize];
-        }
-    }
+struct Options {
+    command: String,
+    key: KeySource,
+    padding: Padding,
+    // explicit IV, otherwise a random one is prepended to the ciphertext
+    iv: Option<[u8; 16]>,
+    // PBKDF2 iterations when encrypting with a password
+    iterations: u32,
+}
 
-    fn inv_sub_word(&mut self) -> () {
-        for i in 0..4 {
-            self.bytes[i] = sbox::INV_SBOX[self.bytes[i] as usize];
+/**
+    Read a line from the terminal with echo turned off
+**/
+fn read_hidden(tty: &mut fs::File, prompt: &str) -> io::Result<String> {
+    tty.write_all(prompt.as_bytes())?;
+
+    // stty changes the terminal on its stdin, never read the password with echo on
+    let status = Command::new("stty").arg("-echo").stdin(tty.try_clone()?).status()
+        .map_err(|e| io::Error::other(format!("could not run stty: {}", e)))?;
+    if !status.success() {
+        return Err(io::Error::other("could not turn off terminal echo"));
+    }
+
+    let mut line = Vec::new();
+    let mut byte = [0u8; 1];
+    let result = loop {
+        match tty.read(&mut byte) {
+            Ok(0) => break Ok(()),
+            Ok(_) if byte[0] == b'\n' => break Ok(()),
+            Ok(_) => line.push(byte[0]),
+            Err(e) => break Err(e),
         }
-    }
-
-    // derived from c implementation on wikipedia
-    fn mix_column(&mut self) -> () {
-        self.bytes[0] = gf_double(self.bytes[0]) ^ self.bytes[3] ^ self.bytes[2] ^ gf_double(self.bytes[1]) ^ self.bytes[1];
-        self.bytes[1] = gf_double(self.bytes[1]) ^ self.bytes[0] ^ self.bytes[3] ^ gf_double(self.bytes[2]) ^ self.bytes[2];
-        self.bytes[2] = gf_double(self.bytes[2]) ^ self.bytes[1] ^ self.bytes[0] ^ gf_double(self.bytes[3]) ^ self.bytes[3];
-        self.bytes[3] = gf_double(self.bytes[3]) ^ self.bytes[2] ^ self.bytes[1] ^ gf_double(self.bytes[0]) ^ self.bytes[0];
-    }
-
-    // derived from https://github.com/boppreh/aes (pure magic)
-    fn inv_mix_column(&mut self) -> () {
-        let u = gf_mult(self.bytes[0] ^ self.bytes[2], 4);
-        let v = gf_mult(self.bytes[1] ^ self.bytes[3], 4);
+    };
 
-        self.bytes[0] ^= u;
-        self.bytes[1] ^= v;
-        self.bytes[2] ^= u;
-        self.bytes[3] ^= v;
+    let restored = Command::new("stty").arg("echo").stdin(tty.try_clone()?).status()?.success();
+    tty.write_all(b"\n")?;
+    result?;
 
-        self.mix_column();
+    if !restored {
+        return Err(io::Error::other("could not turn terminal echo back on"));
     }
-}
 
-
-#[derive(Clone, Copy, Debug)]
-struct Block {
-    // 1D array of 4x4 words
-    words: [Word; 4]
+    let line = String::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(line.trim_end_matches('\r').to_string())
 }
 
-impl std::ops::BitXor for Block {
-    type Output = Self;
+/**
+    Ask for the password on the terminal, twice when it is used to encrypt
+**/
+fn prompt_password(confirm: bool) -> aes::Result<String> {
+    let mut tty = fs::OpenOptions::new().read(true).write(true).open("/dev/tty")?;
 
-    fn bitxor(self, rhs: Self) -> Self {
-        let mut result = self.copy();
-        result ^= rhs;
-        result
+    let password = read_hidden(&mut tty, "Password: ")?;
+    if confirm && read_hidden(&mut tty, "Verify password: ")? != password {
+        return Err(AesError::PasswordMismatch);
     }
-}
 
-impl std::ops::BitXorAssign for Block {
-    fn bitxor_assign(&mut self, rhs: Self) {
-        for i in 0..4 {
-            self.words[i] ^= rhs.words[i];
-        }
-    }
+    Ok(password)
 }
 
-impl Block {
-    fn new(bytes: [u8; 16]) -> Self {
-        let mut words: [Word; 4] = [Word { bytes: [0; 4] }; 4];
-
-        for i in 0..16 {
-            words[i / 4].bytes[i % 4] = bytes[i];
-        }
-
-        Block { words }
-    }
-
-    fn copy(&self) -> Self {
-        Block { words: self.words }
-    }
-
-    fn as_bytes(&self) -> [u8; 16] {
-        let mut bytes: [u8; 16] = [0; 16];
-
-        for i in 0..16 {
-            bytes[i] = self.words[i / 4].bytes[i % 4];
-        }
-
-        bytes
-    }
-
-    fn sub_bytes(self) -> () {
-        for mut w in self.words {
-            w.sub_word();
-        }
-    }
-
-    fn inv_sub_bytes(self) -> () {
-        for mut w in self.words {
-            w.inv_sub_word();
-        }
-    }
-
-    fn shift_rows(self) -> () {
-        for mut r in self.words {
-            r.bytes.rotate_left(1)
-        }
-    }
-
-    fn inv_shift_rows(self) -> () {
-        for mut r in self.words {
-            r.bytes.rotate_right(1)
+/**
+    Derive the key from a password, the salt and iteration count travel in a header
+**/
+fn password_key(password: &str, options: &Options, buffer: &mut Vec<u8>, header: &mut Vec<u8>) -> aes::Result<Vec<u8>> {
+    if password.is_empty() {
+        return Err(AesError::EmptyPassword);
+    }
+
+    let (salt, iterations) = if options.command == "encrypt" {
+        let mut salt = [0u8; 16];
+        os_random(&mut salt)?;
+
+        header.extend_from_slice(PASSWORD_MAGIC);
+        header.extend_from_slice(&options.iterations.to_be_bytes());
+        header.extend_from_slice(&salt);
+        (salt, options.iterations)
+    } else {
+        if buffer.len() < PASSWORD_HEADER_LEN || !buffer.starts_with(PASSWORD_MAGIC) {
+            return Err(AesError::NotPasswordEncrypted);
         }
-    }
 
-    fn mix_columns(self) -> () {
-        for mut w in self.words {
-            w.mix_column();
-        }
-    }
+        let iterations = u32::from_be_bytes(buffer[8..12].try_into().unwrap());
+        let salt: [u8; 16] = buffer[12..PASSWORD_HEADER_LEN].try_into().unwrap();
+        buffer.drain(..PASSWORD_HEADER_LEN);
+        (salt, iterations)
+    };
 
-    fn inv_mix_columns(self) -> () {
-        for mut w in self.words {
-            w.inv_mix_column();
-        }
+    if iterations > MAX_ITERATIONS {
+        return Err(AesError::InvalidIterationCount(iterations));
     }
 
-    fn add_round_key(&mut self, key: Block) -> () {
-        for i in 0..4 {
-            self.words[i] ^= key.words[i];
-        }
-    }
+    // always AES-256
+    pbkdf2_hmac_sha256(password.as_bytes(), &salt, iterations, 32)
 }
 
-fn bytes_to_blocks(ref bytes: &[u8]) -> Vec<Block> {
-    let length = bytes.len() as u8;
-
-    // calculate padding
-    let padding = 4*NB - (length % 4*NB);
-
-    // pad bytes
-    let mut padded_bytes: Vec<u8> = Vec::new();
-    padded_bytes.extend_from_slice(bytes);
-    padded_bytes.extend_from_slice(&vec![padding; padding as usize]);
-
-    // convert bytes to words
-    let mut words: Vec<Word> = Vec::new();
-    for i in 0..padded_bytes.len() / 4 {
-        words.push(
-            Word { bytes: [
-                padded_bytes[(4 * i) as usize],
-                padded_bytes[(4 * i + 1) as usize],
-                padded_bytes[(4 * i + 2) as usize],
-                padded_bytes[(4 * i + 3) as usize]
-            ] }
-        );
-    }
-
-    // convert words to blocks
-    let mut blocks: Vec<Block> = Vec::new();
-    for i in 0..words.len() {
-        if i % 4 == 0 {
-            blocks.push(Block::new([0; 16]));
-        }
-        blocks[i / 4].words[i % 4] = words[i];
-    }
-
-    blocks
+fn env_var(name: &str) -> aes::Result<String> {
+    env::var(name).map_err(|_| AesError::MissingEnvVar(name.to_string()))
 }
 
 /**
-    Round constant for round j
+    Read and decode the key, it must be 16, 24 or 32 bytes
 **/
-fn rcon(j: u8) -> Word {
-    Word { bytes: [R_CON[j as usize], 0, 0, 0] }
-}
-
-fn key_expansion(key: &[u8], nk: u8, nr: u8) -> Vec<Block> {
-
-    // convert key to words
-    let mut w: Vec<Word> = Vec::new();
-    for i in 0..nk {
-        w.push(
-            Word { bytes: [
-                key[(4 * i) as usize],
-                key[(4 * i + 1) as usize],
-                key[(4 * i + 2) as usize],
-                key[(4 * i + 3) as usize]
-            ] }
-        );
-    }
+fn load_key(source: &KeySource) -> aes::Result<Vec<u8>> {
+    let key = match source {
+        KeySource::Text(text) => text.as_bytes().to_vec(),
+        KeySource::Hex(text) => decode_hex(text)?,
+        KeySource::Base64(text) => decode_base64(text)?,
+        KeySource::File(path, format) => format.decode(&fs::read(path)?)?,
+        KeySource::Env(name, format) => format.decode(env_var(name)?.as_bytes())?,
+        KeySource::Password(_) => unreachable!("password keys come from password_key"),
+    };
 
-    // key expansion
-    for i in nk..NB * (nr + 1) {
-        let mut temp = w[(i - 1) as usize].copy();
+    determine_key_length(&key)?;
+    Ok(key)
+}
 
-        if i % nk == 0 {
-            temp.rot_word();
-            temp.sub_word();
-            temp ^= rcon(i / nk -1);
-        } else if nk > 6 && i % nk == 4 {
-            temp.sub_word();
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut positional: Vec<&String> = Vec::new();
+    let mut padding = None;
+    let mut iv = None;
+    let mut key = None;
+    let mut iterations = None;
+    let mut key_format = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--padding" => padding = Some(Padding::from_name(iter.next()?)?),
+            "--iv" => iv = Some(decode_hex(iter.next()?).ok()?.try_into().ok()?),
+            "--key-hex" => key = Some(KeySource::Hex(iter.next()?.clone())),
+            "--key-base64" => key = Some(KeySource::Base64(iter.next()?.clone())),
+            "--key-file" => key = Some(KeySource::File(iter.next()?.clone(), KeyFormat::Raw)),
+            "--key-env" => key = Some(KeySource::Env(iter.next()?.clone(), KeyFormat::Raw)),
+            "--key-format" => key_format = Some(KeyFormat::from_name(iter.next()?)?),
+            "--password" => key = Some(KeySource::Password(None)),
+            "--password-env" => key = Some(KeySource::Password(Some(iter.next()?.clone()))),
+            "--iterations" => iterations = Some(iter.next()?.parse().ok()?),
+            _ => positional.push(arg),
         }
-
-        w.push(w[(i - nk) as usize] ^ temp);
     }
 
-    // convert words to blocks
-    let mut blocks: Vec<Block> = Vec::new();
-    for i in 0..w.len() {
-        if i % 4 == 0 {
-            blocks.push(Block::new([0; 16]));
+    // --key-format only applies to keys read from a file or the environment
+    if let Some(format) = key_format {
+        match &mut key {
+            Some(KeySource::File(_, f)) | Some(KeySource::Env(_, f)) => *f = format,
+            _ => return None,
         }
-        blocks[i / 4].words[i % 4] = w[i];
     }
 
-    blocks
-}
-
-fn cipher(inblock: Block, n_rounds: u8, w: Vec<Block>) -> Block {
-    let mut state = inblock;
-
-    state.add_round_key(w[0]);
+    // the key is either one of the options or the second positional argument
+    let key = match (key, positional.len()) {
+        (Some(key), 1) => key,
+        (None, 2) => KeySource::Text(positional[1].clone()),
+        _ => return None,
+    };
 
-    for i in 1..n_rounds {
-        state.sub_bytes();
-        state.shift_rows();
-        state.mix_columns();
-        state.add_round_key(w[i as usize]);
+    if !matches!(positional[0].as_str(), "encrypt" | "decrypt" | "wrap" | "unwrap") {
+        return None;
     }
 
-    state.sub_bytes();
-    state.shift_rows();
-    state.add_round_key(w[n_rounds as usize]);
-
-    state
-}
-
-fn inv_cipher(inblock: Block, n_rounds: u8, w: Vec<Block>) -> Block {
-    let mut state = inblock;
-
-    state.add_round_key(w[n_rounds as usize]);
-
-    for i in (1..n_rounds).rev() {
-        state.inv_shift_rows();
-        state.inv_sub_bytes();
-        state.add_round_key(w[i as usize]);
-        state.inv_mix_columns();
+    // the password header is only written for encrypt and decrypt
+    if matches!(key, KeySource::Password(_)) && !matches!(positional[0].as_str(), "encrypt" | "decrypt") {
+        return None;
     }
 
-    state.inv_shift_rows();
-    state.inv_sub_bytes();
-    state.add_round_key(w[0]);
-
-    state
-}
-
-fn determine_key_length(key: &[u8]) -> (u8, u8) {
-    match key.len() {
-        16 => (4, 10),
-        24 => (6, 12),
-        32 => (8, 14),
-        _ => panic!("invalid key length"),
+    // and --iterations only means something with a password
+    if iterations.is_some() && !matches!(key, KeySource::Password(_)) {
+        return None;
     }
-}
-
-fn aes(inblock: Block, key: &[u8]) -> Block {
-    let (nk, nr) = determine_key_length(key);
-
-    let w = key_expansion(key, nk, nr);
-
-    cipher(inblock, nr, w)
-}
 
-fn inv_aes(inblock: Block, key: &[u8]) -> Block {
-    let (nk, nr) = determine_key_length(key);
-
-    let w = key_expansion(key, nk, nr);
-
-    inv_cipher(inblock, nr, w)
-}
-
-fn encrypt(bytes: &[u8], key: &[u8], iv: [u8; 16]) -> Vec<u8> {
-    let blocks = bytes_to_blocks(bytes);
-    let mut cipher: Vec<u8> = Vec::new();
-
-    let mut chain = Block::new(iv);
-
-    for block in blocks {
-        chain = aes(block ^ chain, key);
-        cipher.extend_from_slice(&chain.as_bytes());
+    // key wrapping has no IV and does its own padding
+    if (iv.is_some() || padding.is_some()) && matches!(positional[0].as_str(), "wrap" | "unwrap") {
+        return None;
     }
 
-    cipher
+    Some(Options {
+        command: positional[0].clone(),
+        key,
+        padding: padding.unwrap_or_default(),
+        iv,
+        iterations: iterations.unwrap_or(DEFAULT_ITERATIONS),
+    })
 }
 
-fn decrypt(bytes: &[u8], key: &[u8], iv: [u8; 16]) -> Vec<u8> {
-    let blocks = bytes_to_blocks(bytes);
-    let mut plain: Vec<u8> = Vec::new();
-
-    let mut chain = Block::new(iv);
-
-    for block in blocks {
-        plain.extend_from_slice(&(inv_aes(block, key) ^ chain).as_bytes());
-        chain = block;
-    }
-
-    //remove padding
-    let padding = plain[plain.len() - 1];
-    plain.truncate(plain.len() - padding as usize);
-
-    plain
-}
-
-
-fn main() -> io::Result<()> {
+fn run(options: &Options) -> aes::Result<()> {
     // read input from stdin
     let mut buffer = Vec::new();
     io::stdin().read_to_end(&mut buffer)?;
 
-    // where do we get this from?
-    let iv = [0u8; 16];
-
-    // command line arguments
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        println!("Usage: {} <encrypt|decrypt> <key>", args[0]);
-        return Ok(())
-    }
-
-    // convert input to bytes
-    let key: &[u8] = args[2].as_bytes();
+    let mut header = Vec::new();
+    let key = match &options.key {
+        KeySource::Password(variable) => {
+            let password = match variable {
+                Some(name) => env_var(name)?,
+                None => prompt_password(options.command == "encrypt")?,
+            };
+            password_key(&password, options, &mut buffer, &mut header)?
+        }
+        source => load_key(source)?,
+    };
 
-    let result: Vec<u8> = match args[1].as_str() {
-        "encrypt" => encrypt(&mut buffer, key, iv),
-        "decrypt" => decrypt(&mut buffer, key, iv),
-        _ => panic!("invalid command")
+    let result: Vec<u8> = match options.command.as_str() {
+        "encrypt" => match options.iv {
+            Some(iv) => encrypt_padded(&buffer, &key, iv, options.padding)?,
+            None => {
+                // a fresh IV for every message, sent in front of the ciphertext
+                let mut iv = [0u8; 16];
+                os_random(&mut iv)?;
+
+                let mut out = iv.to_vec();
+                out.extend(encrypt_padded(&buffer, &key, iv, options.padding)?);
+                out
+            }
+        },
+        "decrypt" => match options.iv {
+            Some(iv) => decrypt_padded(&buffer, &key, iv, options.padding)?,
+            None => {
+                if buffer.len() < 16 {
+                    return Err(AesError::InvalidCiphertextLength(buffer.len()));
+                }
+
+                let (iv, ciphertext) = buffer.split_at(16);
+                decrypt_padded(ciphertext, &key, iv.try_into().unwrap(), options.padding)?
+            }
+        },
+        // key wrapping always uses RFC 5649 so any key length can be wrapped
+        "wrap" => kw::wrap_pad(&Aes::new(&key)?, &buffer)?,
+        _ => kw::unwrap_pad(&Aes::new(&key)?, &buffer)?,
     };
 
     // write result to stdout
+    io::stdout().write_all(&header)?;
     io::stdout().write_all(&result)?;
 
     Ok(())
 }
+
+fn main() -> ExitCode {
+    // command line arguments
+    let args: Vec<String> = env::args().collect();
+    let options = match parse_args(&args[1..]) {
+        Some(options) => options,
+        None => {
+            eprintln!("Usage: {} [--padding <pkcs7|x923|iso7816|iso10126|none>] [--iv <hex>] <encrypt|decrypt|wrap|unwrap> <key | --key-hex <hex> | --key-base64 <base64> | --key-file <path> | --key-env <var> [--key-format <raw|hex|base64>] | --password | --password-env <var>> [--iterations <n>]", args[0]);
+            return ExitCode::from(2)
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
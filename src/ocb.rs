@@ -0,0 +1,251 @@
+/*!
+    Offset codebook mode, OCB3 (RFC 7253)
+
+    Single-pass authenticated encryption: every block costs one block cipher
+    call, with offsets derived from a precomputed table of L values.
+    Decryption runs the inverse cipher. Nonces are 1 to 15 bytes and tags
+    64, 96 or 128 bits.
+*/
+
+use std::fmt;
+
+use crate::block::gf128_double;
+use crate::cipher::BlockCipher;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    Entries in the L table, enough for any message length
+**/
+const L_TABLE: usize = 64;
+
+fn xor(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    let mut out = *a;
+    for (x, y) in out.iter_mut().zip(b) {
+        *x ^= y;
+    }
+    out
+}
+
+/**
+    A partial block followed by a one bit and zeros
+**/
+fn pad_block(bytes: &[u8]) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    block[..bytes.len()].copy_from_slice(bytes);
+    block[bytes.len()] = 0x80;
+    block
+}
+
+/**
+    AES-OCB3 with a fixed key and tag length
+**/
+#[derive(Clone)]
+pub struct Ocb<C: BlockCipher> {
+    cipher: C,
+    tag_len: usize,
+    l_star: [u8; 16],
+    l_dollar: [u8; 16],
+    // L_i for i = 0, 1, ...
+    l: Vec<[u8; 16]>,
+}
+
+impl<C: BlockCipher + fmt::Debug> fmt::Debug for Ocb<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the L values are left out, they are encryptions under the key
+        f.debug_struct("Ocb")
+            .field("cipher", &self.cipher)
+            .field("tag_len", &self.tag_len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> Ocb<C> {
+    pub fn new(cipher: C, tag_len: usize) -> Result<Self> {
+        if !matches!(tag_len, 8 | 12 | 16) {
+            return Err(AesError::InvalidTagLength(tag_len));
+        }
+
+        let l_star = cipher.encrypt_block(&[0; 16]);
+        let l_dollar = gf128_double(l_star);
+
+        let mut l = vec![gf128_double(l_dollar)];
+        for i in 1..L_TABLE {
+            l.push(gf128_double(l[i - 1]));
+        }
+
+        Ok(Ocb { cipher, tag_len, l_star, l_dollar, l })
+    }
+
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /**
+        L value for block number i, counting from 1
+    **/
+    fn l_ntz(&self, i: usize) -> &[u8; 16] {
+        &self.l[i.trailing_zeros() as usize]
+    }
+
+    /**
+        Initial offset from the nonce, RFC 7253 section 4.2
+    **/
+    fn initial_offset(&self, nonce: &[u8]) -> Result<[u8; 16]> {
+        if nonce.is_empty() || nonce.len() > 15 {
+            return Err(AesError::InvalidNonceLength(nonce.len()));
+        }
+
+        // tag length, zeros, a one bit, then the nonce
+        let mut full = [0u8; 16];
+        full[16 - nonce.len()..].copy_from_slice(nonce);
+        full[15 - nonce.len()] |= 1;
+        full[0] |= (((self.tag_len * 8) % 128) as u8) << 1;
+
+        let bottom = (full[15] & 0x3f) as usize;
+        full[15] &= 0xc0;
+        let ktop = self.cipher.encrypt_block(&full);
+
+        // Stretch = Ktop || (Ktop[1..64] xor Ktop[9..72])
+        let mut stretch = [0u8; 24];
+        stretch[..16].copy_from_slice(&ktop);
+        for i in 0..8 {
+            stretch[16 + i] = ktop[i] ^ ktop[i + 1];
+        }
+
+        // bits bottom .. bottom + 128 of the stretch
+        let (bytes, bits) = (bottom / 8, bottom % 8);
+        let mut offset = [0u8; 16];
+        for (i, o) in offset.iter_mut().enumerate() {
+            *o = stretch[i + bytes] << bits;
+            if bits > 0 {
+                *o |= stretch[i + bytes + 1] >> (8 - bits);
+            }
+        }
+
+        Ok(offset)
+    }
+
+    /**
+        HASH of the associated data, RFC 7253 section 4.1
+    **/
+    fn hash(&self, aad: &[u8]) -> [u8; 16] {
+        let mut sum = [0u8; 16];
+        let mut offset = [0u8; 16];
+
+        let mut chunks = aad.chunks_exact(16);
+        for (i, chunk) in chunks.by_ref().enumerate() {
+            offset = xor(&offset, self.l_ntz(i + 1));
+            let input = xor(chunk.try_into().unwrap(), &offset);
+            sum = xor(&sum, &self.cipher.encrypt_block(&input));
+        }
+
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            offset = xor(&offset, &self.l_star);
+            let input = xor(&pad_block(rest), &offset);
+            sum = xor(&sum, &self.cipher.encrypt_block(&input));
+        }
+
+        sum
+    }
+
+    /**
+        Shared pass over the text, returns the checksum and final offset
+    **/
+    fn process(&self, nonce: &[u8], buf: &mut [u8], decrypt: bool) -> Result<([u8; 16], [u8; 16])> {
+        let mut offset = self.initial_offset(nonce)?;
+        let mut checksum = [0u8; 16];
+
+        let full = buf.len() / 16;
+        for i in 0..full {
+            let block: [u8; 16] = buf[16 * i..16 * i + 16].try_into().unwrap();
+            offset = xor(&offset, self.l_ntz(i + 1));
+
+            let input = xor(&block, &offset);
+            let output = if decrypt {
+                xor(&self.cipher.decrypt_block(&input), &offset)
+            } else {
+                xor(&self.cipher.encrypt_block(&input), &offset)
+            };
+
+            // the checksum is always over the plaintext
+            checksum = xor(&checksum, if decrypt { &output } else { &block });
+            buf[16 * i..16 * i + 16].copy_from_slice(&output);
+        }
+
+        let rest = &mut buf[16 * full..];
+        if !rest.is_empty() {
+            offset = xor(&offset, &self.l_star);
+            let pad = self.cipher.encrypt_block(&offset);
+
+            if !decrypt {
+                checksum = xor(&checksum, &pad_block(rest));
+            }
+            for (byte, p) in rest.iter_mut().zip(pad) {
+                *byte ^= p;
+            }
+            if decrypt {
+                checksum = xor(&checksum, &pad_block(rest));
+            }
+        }
+
+        Ok((checksum, offset))
+    }
+
+    fn tag(&self, checksum: &[u8; 16], offset: &[u8; 16], aad: &[u8]) -> Vec<u8> {
+        let input = xor(&xor(checksum, offset), &self.l_dollar);
+        let tag = xor(&self.cipher.encrypt_block(&input), &self.hash(aad));
+
+        tag[..self.tag_len].to_vec()
+    }
+
+    /**
+        Encrypt buf in place and return the detached tag
+    **/
+    pub fn encrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> Result<Vec<u8>> {
+        let (checksum, offset) = self.process(nonce, buf, false)?;
+
+        Ok(self.tag(&checksum, &offset, aad))
+    }
+
+    /**
+        Decrypt buf in place, it is zeroed again if the tag does not match
+    **/
+    pub fn decrypt_in_place_detached(&self, nonce: &[u8], aad: &[u8], buf: &mut [u8], tag: &[u8]) -> Result<()> {
+        let (checksum, offset) = self.process(nonce, buf, true)?;
+
+        if !ct_eq(&self.tag(&checksum, &offset, aad), tag) {
+            buf.fill(0);
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Encrypt and append the tag
+    **/
+    pub fn encrypt(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+
+        Ok(out)
+    }
+
+    /**
+        Verify the trailing tag and decrypt
+    **/
+    pub fn decrypt(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < self.tag_len {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - self.tag_len);
+        let mut out = body.to_vec();
+        self.decrypt_in_place_detached(nonce, aad, &mut out, tag)?;
+
+        Ok(out)
+    }
+}
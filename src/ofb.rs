@@ -0,0 +1,120 @@
+/*!
+    Output feedback mode (SP 800-38A section 6.4)
+
+    The keystream only depends on the key and IV, so `Ofb` can stop at any
+    byte and carry on later, either by keeping the object around or by saving
+    its `OfbState` and resuming from it. `OfbWriter` applies the keystream to
+    everything written through it.
+*/
+
+use std::io::{self, Write};
+
+use crate::cipher::BlockCipher;
+use crate::error::{AesError, Result};
+
+/**
+    Position in an OFB keystream, enough to resume it under the same key
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OfbState {
+    // last output block, the next one is its encryption
+    pub register: [u8; 16],
+    // bytes of register already used as keystream
+    pub offset: usize,
+}
+
+/**
+    Resumable OFB keystream over a block cipher
+**/
+#[derive(Clone, Debug)]
+pub struct Ofb<C: BlockCipher> {
+    cipher: C,
+    state: OfbState,
+}
+
+impl<C: BlockCipher> Ofb<C> {
+    pub fn new(cipher: C, iv: [u8; 16]) -> Self {
+        // a fully used register makes the first byte generate E(iv)
+        Ofb { cipher, state: OfbState { register: iv, offset: 16 } }
+    }
+
+    /**
+        Continue a keystream saved with `state`, the offset can be at most 16
+    **/
+    pub fn resume(cipher: C, state: OfbState) -> Result<Self> {
+        if state.offset > 16 {
+            return Err(AesError::InvalidStateOffset(state.offset));
+        }
+
+        Ok(Ofb { cipher, state })
+    }
+
+    pub fn state(&self) -> OfbState {
+        self.state
+    }
+
+    /**
+        XOR the keystream into data, encryption and decryption are the same
+    **/
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            if self.state.offset == 16 {
+                self.state.register = self.cipher.encrypt_block(&self.state.register);
+                self.state.offset = 0;
+            }
+
+            *byte ^= self.state.register[self.state.offset];
+            self.state.offset += 1;
+        }
+    }
+
+    /**
+        Wrap a writer so everything written to it is en/decrypted first
+    **/
+    pub fn writer<W: Write>(self, inner: W) -> OfbWriter<C, W> {
+        OfbWriter { ofb: self, inner }
+    }
+}
+
+/**
+    Writer applying an OFB keystream to the bytes passing through
+**/
+#[derive(Debug)]
+pub struct OfbWriter<C: BlockCipher, W: Write> {
+    ofb: Ofb<C>,
+    inner: W,
+}
+
+impl<C: BlockCipher, W: Write> OfbWriter<C, W> {
+    pub fn state(&self) -> OfbState {
+        self.ofb.state()
+    }
+
+    /**
+        Give back the keystream and the wrapped writer
+    **/
+    pub fn into_parts(self) -> (Ofb<C>, W) {
+        (self.ofb, self.inner)
+    }
+}
+
+impl<C: BlockCipher, W: Write> Write for OfbWriter<C, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // write everything, and rewind on failure, so the keystream never
+        // runs ahead of the output
+        let saved = self.ofb.state;
+        let mut data = buf.to_vec();
+        self.ofb.apply_keystream(&mut data);
+
+        if let Err(e) = self.inner.write_all(&data) {
+            self.ofb.state = saved;
+            return Err(e);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
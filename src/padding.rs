@@ -0,0 +1,120 @@
+use crate::error::{AesError, Result};
+use crate::rand::os_random;
+
+/**
+    Block size in bytes
+**/
+const BLOCK: usize = 16;
+
+/**
+    Padding schemes for block-aligned modes
+**/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Padding {
+    // n bytes of value n
+    #[default]
+    Pkcs7,
+    // n - 1 zero bytes followed by n
+    AnsiX923,
+    // 0x80 followed by zero bytes
+    Iso7816,
+    // n - 1 random bytes followed by n
+    Iso10126,
+    // input must already be a multiple of the block size
+    NoPadding,
+}
+
+impl Padding {
+    /**
+        Look up a scheme by its command line name
+    **/
+    pub fn from_name(name: &str) -> Option<Padding> {
+        match name {
+            "pkcs7" => Some(Padding::Pkcs7),
+            "x923" => Some(Padding::AnsiX923),
+            "iso7816" => Some(Padding::Iso7816),
+            "iso10126" => Some(Padding::Iso10126),
+            "none" => Some(Padding::NoPadding),
+            _ => None,
+        }
+    }
+
+    /**
+        Pad bytes to a multiple of the block size, adding a full block if aligned
+    **/
+    pub fn pad(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let n = BLOCK - bytes.len() % BLOCK;
+
+        let mut padded: Vec<u8> = Vec::with_capacity(bytes.len() + n);
+        padded.extend_from_slice(bytes);
+
+        match self {
+            Padding::Pkcs7 => padded.resize(bytes.len() + n, n as u8),
+            Padding::AnsiX923 => {
+                padded.resize(bytes.len() + n - 1, 0);
+                padded.push(n as u8);
+            }
+            Padding::Iso7816 => {
+                padded.push(0x80);
+                padded.resize(bytes.len() + n, 0);
+            }
+            Padding::Iso10126 => {
+                let mut filler = vec![0u8; n - 1];
+                os_random(&mut filler)?;
+                padded.extend_from_slice(&filler);
+                padded.push(n as u8);
+            }
+            Padding::NoPadding => {
+                if n != BLOCK {
+                    return Err(AesError::InvalidPlaintextLength(bytes.len()));
+                }
+            }
+        }
+
+        Ok(padded)
+    }
+
+    /**
+        Strip and validate the padding of decrypted bytes
+    **/
+    pub fn unpad<'a>(&self, bytes: &'a [u8]) -> Result<&'a [u8]> {
+        if *self == Padding::NoPadding {
+            return Ok(bytes);
+        }
+
+        if bytes.is_empty() || !bytes.len().is_multiple_of(BLOCK) {
+            return Err(AesError::BadPadding);
+        }
+
+        let last = bytes[bytes.len() - 1] as usize;
+
+        let n = match self {
+            Padding::Iso7816 => {
+                // skip trailing zeros of the last block, then expect the marker
+                let zeros = bytes.iter().rev().take(BLOCK).take_while(|b| **b == 0).count();
+                if zeros == BLOCK || bytes[bytes.len() - 1 - zeros] != 0x80 {
+                    return Err(AesError::BadPadding);
+                }
+                zeros + 1
+            }
+            _ => {
+                if last == 0 || last > BLOCK {
+                    return Err(AesError::BadPadding);
+                }
+
+                let filler = &bytes[bytes.len() - last..bytes.len() - 1];
+                let valid = match self {
+                    Padding::Pkcs7 => filler.iter().all(|b| *b as usize == last),
+                    Padding::AnsiX923 => filler.iter().all(|b| *b == 0),
+                    _ => true,
+                };
+                if !valid {
+                    return Err(AesError::BadPadding);
+                }
+                last
+            }
+        };
+
+        Ok(&bytes[..bytes.len() - n])
+    }
+}
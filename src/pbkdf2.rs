@@ -0,0 +1,45 @@
+/*!
+    PBKDF2 with HMAC-SHA256 as the PRF (RFC 8018 section 5.2)
+
+    Derives keys from passwords. The salt should be random and at least 16
+    bytes, and the iteration count as high as the caller can afford.
+*/
+
+use crate::error::{AesError, Result};
+use crate::hmac::HmacSha256;
+
+/**
+    Derive len bytes from a password and salt
+**/
+pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> Result<Vec<u8>> {
+    if iterations == 0 {
+        return Err(AesError::InvalidIterationCount(iterations));
+    }
+
+    let prf = HmacSha256::new(password);
+    let mut out: Vec<u8> = Vec::with_capacity(len.div_ceil(32) * 32);
+
+    // T_i = U_1 ^ U_2 ^ ... ^ U_c with U_1 = PRF(P, S || INT(i))
+    for i in 1..=len.div_ceil(32) as u32 {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&i.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u;
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize();
+
+            for (t, u) in t.iter_mut().zip(u) {
+                *t ^= u;
+            }
+        }
+
+        out.extend_from_slice(&t);
+    }
+
+    out.truncate(len);
+    Ok(out)
+}
@@ -0,0 +1,60 @@
+/*!
+    POLYVAL, the universal hash of AES-GCM-SIV (RFC 8452 section 3)
+
+    Computed through GHASH using the relation of RFC 8452 Appendix A: with
+    the key multiplied by x and every block byte-reversed, POLYVAL is GHASH
+    with its output byte-reversed. Reading blocks little-endian does the
+    reversal.
+*/
+
+use std::fmt;
+
+use crate::ghash::{gf128_mul, R};
+
+/**
+    Incremental POLYVAL under a hash key
+**/
+#[derive(Clone)]
+pub struct Polyval {
+    h: u128,
+    s: u128,
+}
+
+impl fmt::Debug for Polyval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the hash subkey and the accumulator are left out
+        f.debug_struct("Polyval").finish_non_exhaustive()
+    }
+}
+
+impl Polyval {
+    pub fn new(h: &[u8; 16]) -> Self {
+        // mulX_GHASH(ByteReverse(H))
+        let h = u128::from_le_bytes(*h);
+        let h = (h >> 1) ^ (R & 0u128.wrapping_sub(h & 1));
+
+        Polyval { h, s: 0 }
+    }
+
+    /**
+        Absorb one block
+    **/
+    pub fn update_block(&mut self, block: &[u8; 16]) {
+        self.s = gf128_mul(self.s ^ u128::from_le_bytes(*block), self.h);
+    }
+
+    /**
+        Absorb bytes, zero-padding the last partial block
+    **/
+    pub fn update_padded(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.update_block(&block);
+        }
+    }
+
+    pub fn finalize(&self) -> [u8; 16] {
+        self.s.to_le_bytes()
+    }
+}
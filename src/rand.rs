@@ -0,0 +1,9 @@
+use std::fs::File;
+use std::io::{self, Read};
+
+/**
+    Fill buf with bytes from the operating system's random source
+**/
+pub fn os_random(buf: &mut [u8]) -> io::Result<()> {
+    File::open("/dev/urandom")?.read_exact(buf)
+}
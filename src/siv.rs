@@ -0,0 +1,144 @@
+/*!
+    Synthetic initialization vector mode (RFC 5297)
+
+    Deterministic authenticated encryption: the IV is a CMAC-based PRF
+    (S2V) over the associated data components and the plaintext, and is
+    prepended to the CTR ciphertext. A nonce, if used, is passed as the last
+    associated data component. Keys are double length, the first half keys
+    S2V and the second half keys CTR.
+*/
+
+use crate::block::gf128_double;
+use crate::cipher::{Aes, BlockCipher};
+use crate::cmac::cmac;
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+use crate::key::determine_siv_key_length;
+
+fn xor_into(a: &mut [u8; 16], b: &[u8; 16]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+/**
+    AES-SIV with a 256, 384 or 512-bit key
+**/
+#[derive(Clone, Debug)]
+pub struct Siv {
+    mac: Aes,
+    ctr: Aes,
+}
+
+impl Siv {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        determine_siv_key_length(key)?;
+
+        let (mac, ctr) = key.split_at(key.len() / 2);
+        Ok(Siv { mac: Aes::new(mac)?, ctr: Aes::new(ctr)? })
+    }
+
+    /**
+        S2V over the associated data components followed by the plaintext
+    **/
+    fn s2v(&self, ad: &[&[u8]], plaintext: &[u8]) -> [u8; 16] {
+        let mut d = cmac(&self.mac, &[0; 16]);
+
+        for component in ad {
+            d = gf128_double(d);
+            xor_into(&mut d, &cmac(&self.mac, component));
+        }
+
+        let t: Vec<u8> = if plaintext.len() >= 16 {
+            // xorend: D goes into the last 16 bytes
+            let mut t = plaintext.to_vec();
+            let start = t.len() - 16;
+            for (x, y) in t[start..].iter_mut().zip(d) {
+                *x ^= y;
+            }
+            t
+        } else {
+            let mut padded = [0u8; 16];
+            padded[..plaintext.len()].copy_from_slice(plaintext);
+            padded[plaintext.len()] = 0x80;
+
+            let mut t = gf128_double(d);
+            xor_into(&mut t, &padded);
+            t.to_vec()
+        };
+
+        cmac(&self.mac, &t)
+    }
+
+    /**
+        CTR keyed by the second half, starting at V with bits 31 and 63 cleared
+    **/
+    fn ctr(&self, v: &[u8; 16], data: &mut [u8]) {
+        let mut q = *v;
+        q[8] &= 0x7f;
+        q[12] &= 0x7f;
+        let q = u128::from_be_bytes(q);
+
+        for (i, chunk) in data.chunks_mut(16).enumerate() {
+            let counter = q.wrapping_add(i as u128).to_be_bytes();
+            let keystream = self.ctr.encrypt_block(&counter);
+            for (byte, k) in chunk.iter_mut().zip(keystream) {
+                *byte ^= k;
+            }
+        }
+    }
+
+    /**
+        Encrypt buf in place and return the detached synthetic IV
+    **/
+    pub fn encrypt_in_place_detached(&self, ad: &[&[u8]], buf: &mut [u8]) -> Result<[u8; 16]> {
+        let v = self.s2v(ad, buf);
+        self.ctr(&v, buf);
+
+        Ok(v)
+    }
+
+    /**
+        Decrypt buf in place, it is zeroed again if the synthetic IV does not match
+    **/
+    pub fn decrypt_in_place_detached(&self, ad: &[&[u8]], buf: &mut [u8], v: &[u8]) -> Result<()> {
+        let v: [u8; 16] = v.try_into().map_err(|_| AesError::InvalidTagLength(v.len()))?;
+
+        self.ctr(&v, buf);
+
+        if !ct_eq(&self.s2v(ad, buf), &v) {
+            buf.fill(0);
+            return Err(AesError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+
+    /**
+        Encrypt and return V followed by the ciphertext
+    **/
+    pub fn encrypt(&self, ad: &[&[u8]], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut out = vec![0u8; 16];
+        out.extend_from_slice(plaintext);
+
+        let v = self.encrypt_in_place_detached(ad, &mut out[16..])?;
+        out[..16].copy_from_slice(&v);
+
+        Ok(out)
+    }
+
+    /**
+        Decrypt and check the synthetic IV, no plaintext is returned on failure
+    **/
+    pub fn decrypt(&self, ad: &[&[u8]], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < 16 {
+            return Err(AesError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let (v, body) = ciphertext.split_at(16);
+        let mut plain = body.to_vec();
+        self.decrypt_in_place_detached(ad, &mut plain, v)?;
+
+        Ok(plain)
+    }
+}
@@ -0,0 +1,156 @@
+/*!
+    XTS-AES for sector based storage (IEEE 1619, SP 800-38E)
+
+    Each data unit (sector) is encrypted in place under a tweak derived from
+    its data unit number. Data units that are not a multiple of 16 bytes use
+    ciphertext stealing, so the ciphertext is exactly as long as the
+    plaintext.
+*/
+
+use crate::cipher::{Aes, BlockCipher};
+use crate::ct::ct_eq;
+use crate::error::{AesError, Result};
+
+/**
+    Largest data unit allowed by IEEE 1619, 2^20 blocks
+**/
+const MAX_DATA_UNIT: usize = 16 << 20;
+
+/**
+    Multiply a tweak by the primitive element in GF(2^128), little-endian
+**/
+fn mul_alpha(t: &mut [u8; 16]) {
+    let value = u128::from_le_bytes(*t);
+    let carry = (value >> 127) as u8;
+    let mut doubled = (value << 1).to_le_bytes();
+    doubled[0] ^= 0x87 & 0u8.wrapping_sub(carry);
+    *t = doubled;
+}
+
+fn xor_block(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    let mut out = *a;
+    for (x, y) in out.iter_mut().zip(b) {
+        *x ^= y;
+    }
+    out
+}
+
+/**
+    XTS with a data key and an independent tweak key
+**/
+#[derive(Clone, Debug)]
+pub struct Xts<C: BlockCipher> {
+    data: C,
+    tweak: C,
+}
+
+impl Xts<Aes> {
+    /**
+        Split a 32 or 64 byte key into distinct data key and tweak key
+    **/
+    pub fn from_key(key: &[u8]) -> Result<Self> {
+        if key.len() != 32 && key.len() != 64 {
+            return Err(AesError::InvalidKeyLength(key.len()));
+        }
+
+        let (data, tweak) = key.split_at(key.len() / 2);
+        if ct_eq(data, tweak) {
+            return Err(AesError::DuplicateXtsKey);
+        }
+
+        Ok(Xts::new(Aes::new(data)?, Aes::new(tweak)?))
+    }
+}
+
+impl<C: BlockCipher> Xts<C> {
+    pub fn new(data: C, tweak: C) -> Self {
+        Xts { data, tweak }
+    }
+
+    fn initial_tweak(&self, sector: u128) -> [u8; 16] {
+        self.tweak.encrypt_block(&sector.to_le_bytes())
+    }
+
+    fn encrypt_block(&self, block: &[u8], t: &[u8; 16]) -> [u8; 16] {
+        let input = xor_block(block.try_into().unwrap(), t);
+        xor_block(&self.data.encrypt_block(&input), t)
+    }
+
+    fn decrypt_block(&self, block: &[u8], t: &[u8; 16]) -> [u8; 16] {
+        let input = xor_block(block.try_into().unwrap(), t);
+        xor_block(&self.data.decrypt_block(&input), t)
+    }
+
+    /**
+        Encrypt one data unit in place
+    **/
+    pub fn encrypt_sector(&self, sector: u128, buf: &mut [u8]) -> Result<()> {
+        if !(16..=MAX_DATA_UNIT).contains(&buf.len()) {
+            return Err(AesError::InvalidPlaintextLength(buf.len()));
+        }
+
+        let full = buf.len() / 16;
+        let tail = buf.len() % 16;
+        let mut t = self.initial_tweak(sector);
+
+        // with a partial tail, the last full block takes part in the stealing
+        let plain = if tail == 0 { full } else { full - 1 };
+        for i in 0..plain {
+            let block = self.encrypt_block(&buf[16 * i..16 * i + 16], &t);
+            buf[16 * i..16 * i + 16].copy_from_slice(&block);
+            mul_alpha(&mut t);
+        }
+
+        if tail != 0 {
+            let last = 16 * plain;
+            let cc = self.encrypt_block(&buf[last..last + 16], &t);
+            mul_alpha(&mut t);
+
+            // the partial plaintext borrows the end of CC
+            let mut pp = cc;
+            pp[..tail].copy_from_slice(&buf[last + 16..]);
+
+            buf[last + 16..].copy_from_slice(&cc[..tail]);
+            buf[last..last + 16].copy_from_slice(&self.encrypt_block(&pp, &t));
+        }
+
+        Ok(())
+    }
+
+    /**
+        Decrypt one data unit in place
+    **/
+    pub fn decrypt_sector(&self, sector: u128, buf: &mut [u8]) -> Result<()> {
+        if !(16..=MAX_DATA_UNIT).contains(&buf.len()) {
+            return Err(AesError::InvalidCiphertextLength(buf.len()));
+        }
+
+        let full = buf.len() / 16;
+        let tail = buf.len() % 16;
+        let mut t = self.initial_tweak(sector);
+
+        let plain = if tail == 0 { full } else { full - 1 };
+        for i in 0..plain {
+            let block = self.decrypt_block(&buf[16 * i..16 * i + 16], &t);
+            buf[16 * i..16 * i + 16].copy_from_slice(&block);
+            mul_alpha(&mut t);
+        }
+
+        if tail != 0 {
+            let last = 16 * plain;
+            let t_last = t;
+            mul_alpha(&mut t);
+
+            // the last full ciphertext block was made with the later tweak
+            let pp = self.decrypt_block(&buf[last..last + 16], &t);
+
+            let mut cc = pp;
+            cc[..tail].copy_from_slice(&buf[last + 16..]);
+
+            buf[last + 16..].copy_from_slice(&pp[..tail]);
+            buf[last..last + 16].copy_from_slice(&self.decrypt_block(&cc, &t_last));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+// Tests for the Aead trait, run once per mode through the same generic code
+
+mod common;
+
+use aes::{Aead, Aes128, Aes256, AesError, Ccm, Eax, Gcm, GcmSiv, Ocb, Siv};
+use common::hex;
+
+static KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f
+                    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f";
+
+fn round_trip<A: Aead>(key_len: usize) {
+    let aead = A::from_key(&hex(KEY)[..key_len]).unwrap();
+    let nonce = vec![0x5a; aead.nonce_len()];
+    let aad = b"header";
+
+    for len in [0, 1, 15, 16, 17, 64] {
+        let plaintext: Vec<u8> = (0..len as u8).collect();
+
+        let sealed = aead.seal(&nonce, aad, &plaintext).unwrap();
+        assert_eq!(sealed.len(), len + aead.tag_len());
+        assert_eq!(aead.open(&nonce, aad, &sealed).unwrap(), plaintext);
+
+        // in place gives the same bytes
+        let mut buf = plaintext.clone();
+        aead.seal_in_place(&nonce, aad, &mut buf).unwrap();
+        assert_eq!(buf, sealed);
+        aead.open_in_place(&nonce, aad, &mut buf).unwrap();
+        assert_eq!(buf, plaintext);
+
+        // detached
+        let mut buf = plaintext.clone();
+        let tag = aead.seal_in_place_detached(&nonce, aad, &mut buf).unwrap();
+        assert_eq!(tag.len(), aead.tag_len());
+        aead.open_in_place_detached(&nonce, aad, &mut buf, &tag).unwrap();
+        assert_eq!(buf, plaintext);
+
+        // any change to the ciphertext, tag or associated data is rejected
+        let mut forged = sealed.clone();
+        forged[0] ^= 1;
+        assert!(matches!(aead.open(&nonce, aad, &forged), Err(AesError::AuthenticationFailed)));
+        assert!(matches!(aead.open(&nonce, b"Header", &sealed), Err(AesError::AuthenticationFailed)));
+    }
+
+    assert!(matches!(aead.open(&nonce, aad, &[0; 3]), Err(AesError::InvalidCiphertextLength(3))));
+}
+
+#[test]
+fn every_mode() {
+    round_trip::<Gcm<Aes128>>(16);
+    round_trip::<Gcm<Aes256>>(32);
+    round_trip::<Ccm<Aes128>>(16);
+    round_trip::<Eax<Aes128>>(16);
+    round_trip::<Ocb<Aes128>>(16);
+    round_trip::<Siv>(32);
+    round_trip::<Siv>(64);
+    round_trip::<GcmSiv>(16);
+    round_trip::<GcmSiv>(32);
+}
+
+#[test]
+fn matches_inherent_methods() {
+    let key = hex(KEY);
+    let nonce = [7u8; 12];
+    let plaintext = b"attack at dawn";
+
+    let gcm = Gcm::new(Aes128::new(&key[..16].try_into().unwrap()));
+    assert_eq!(Aead::seal(&gcm, &nonce, b"ad", plaintext).unwrap(), gcm.encrypt(&nonce, b"ad", plaintext).unwrap());
+
+    let ccm = <Ccm<Aes128> as Aead>::from_key(&key[..16]).unwrap();
+    assert_eq!(ccm.seal(&nonce, b"ad", plaintext).unwrap(), ccm.encrypt(&nonce, b"ad", plaintext).unwrap());
+
+    // SIV keeps the RFC 5297 layout, the nonce is the last component
+    let siv = Siv::new(&key[..32]).unwrap();
+    let sealed = siv.seal(&nonce, b"ad", plaintext).unwrap();
+    assert_eq!(sealed, siv.encrypt(&[b"ad", &nonce], plaintext).unwrap());
+    assert_eq!(siv.decrypt(&[b"ad", &nonce], &sealed).unwrap(), plaintext);
+}
+
+#[test]
+fn key_setup() {
+    assert!(matches!(<Gcm<Aes128> as Aead>::from_key(&[0; 24]), Err(AesError::InvalidKeyLength(24))));
+    assert!(matches!(<Ocb<Aes256> as Aead>::from_key(&[0; 16]), Err(AesError::InvalidKeyLength(16))));
+    assert!(matches!(<Siv as Aead>::from_key(&[0; 16]), Err(AesError::InvalidKeyLength(16))));
+    assert!(matches!(<GcmSiv as Aead>::from_key(&[0; 24]), Err(AesError::InvalidKeyLength(24))));
+}
+
+#[test]
+fn trait_objects() {
+    let key = hex(KEY);
+    let modes: Vec<Box<dyn Aead>> = vec![
+        Box::new(Gcm::new(Aes128::new(&key[..16].try_into().unwrap()))),
+        Box::new(Ccm::new(Aes128::new(&key[..16].try_into().unwrap()), 13, 8).unwrap()),
+        Box::new(GcmSiv::new(&key[..32]).unwrap()),
+    ];
+
+    for aead in modes {
+        let nonce = vec![1; aead.nonce_len()];
+        let sealed = aead.seal(&nonce, b"", b"message").unwrap();
+        assert_eq!(sealed.len(), 7 + aead.tag_len());
+        assert_eq!(aead.open(&nonce, b"", &sealed).unwrap(), b"message");
+    }
+}
@@ -0,0 +1,148 @@
+// CCM tests with the RFC 3610 packet vectors
+
+mod common;
+
+use aes::{Aes128, AesError, Ccm};
+use common::{hex, hex16};
+
+static KEY: &str = "c0c1c2c3c4c5c6c7c8c9cacbcccdcecf";
+
+// nonce, aad, plaintext, tag length, ciphertext || tag
+static VECTORS: [(&str, &str, &str, usize, &str); 12] = [
+    // packet vector #1
+    ("00000003020100a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+     8,
+     "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0"),
+    // packet vector #2
+    ("00000004030201a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     8,
+     "72c91a36e135f8cf291ca894085c87e3cc15c439c9e43a3ba091d56e10400916"),
+    // packet vector #3
+    ("00000005040302a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+     8,
+     "51b1e5f44a197d1da46b0f8e2d282ae871e838bb64da8596574adaa76fbd9fb0c5"),
+    // packet vector #4
+    ("00000006050403a0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e",
+     8,
+     "a28c6865939a9a79faaa5c4c2a9d4a91cdac8c96c861b9c9e61ef1"),
+    // packet vector #5
+    ("00000007060504a0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     8,
+     "dcf1fb7b5d9e23fb9d4e131253658ad86ebdca3e51e83f077d9c2d93"),
+    // packet vector #6
+    ("00000008070605a0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+     8,
+     "6fc1b011f006568b5171a42d953d469b2570a4bd87405a0443ac91cb94"),
+    // packet vector #7
+    ("00000009080706a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+     10,
+     "0135d1b2c95f41d5d1d4fec185d166b8094e999dfed96c048c56602c97acbb7490"),
+    // packet vector #8
+    ("0000000a090807a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     10,
+     "7b75399ac0831dd2f0bbd75879a2fd8f6cae6b6cd9b7db24c17b4433f434963f34b4"),
+    // packet vector #9
+    ("0000000b0a0908a0a1a2a3a4a5",
+     "0001020304050607",
+     "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+     10,
+     "82531a60cc24945a4b8279181ab5c84df21ce7f9b73f42e197ea9c07e56b5eb17e5f4e"),
+    // packet vector #10
+    ("0000000c0b0a09a0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e",
+     10,
+     "07342594157785152b074098330abb141b947b566aa9406b4d999988dd"),
+    // packet vector #11
+    ("0000000d0c0b0aa0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     10,
+     "676bb20380b0e301e8ab79590a396da78b834934f53aa2e9107a8b6c022c"),
+    // packet vector #12
+    ("0000000e0d0c0ba0a1a2a3a4a5",
+     "000102030405060708090a0b",
+     "0c0d0e0f101112131415161718191a1b1c1d1e1f20",
+     10,
+     "c0ffa0d6f05bdb67f24d43a4338d2aa4bed7b20e43cd1aa31662e7ad65d6db"),
+];
+
+#[test]
+fn rfc3610_vectors() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    for (nonce, aad, plaintext, tag_len, sealed) in VECTORS {
+        let ccm = Ccm::new(&aes, 13, tag_len).unwrap();
+
+        assert_eq!(ccm.encrypt(&hex(nonce), &hex(aad), &hex(plaintext)).unwrap(), hex(sealed));
+        assert_eq!(ccm.decrypt(&hex(nonce), &hex(aad), &hex(sealed)).unwrap(), hex(plaintext));
+    }
+}
+
+#[test]
+fn rejects_forgeries() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    let (nonce, aad, _, tag_len, sealed) = VECTORS[0];
+    let ccm = Ccm::new(&aes, 13, tag_len).unwrap();
+
+    for i in [0, hex(sealed).len() - 1] {
+        let mut flipped = hex(sealed);
+        flipped[i] ^= 1;
+        assert!(matches!(ccm.decrypt(&hex(nonce), &hex(aad), &flipped), Err(AesError::AuthenticationFailed)));
+    }
+
+    let sealed = hex(sealed);
+    let (body, tag) = sealed.split_at(sealed.len() - tag_len);
+    let mut buf = body.to_vec();
+    assert!(ccm.decrypt_in_place_detached(&hex(nonce), &[], &mut buf, tag).is_err());
+    assert!(buf.iter().all(|b| *b == 0));
+}
+
+#[test]
+fn parameter_limits() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    assert!(matches!(Ccm::new(&aes, 6, 16), Err(AesError::InvalidNonceLength(6))));
+    assert!(matches!(Ccm::new(&aes, 14, 16), Err(AesError::InvalidNonceLength(14))));
+    assert!(matches!(Ccm::new(&aes, 13, 2), Err(AesError::InvalidTagLength(2))));
+    assert!(matches!(Ccm::new(&aes, 13, 7), Err(AesError::InvalidTagLength(7))));
+    assert!(matches!(Ccm::new(&aes, 13, 18), Err(AesError::InvalidTagLength(18))));
+
+    // nonce has to match the configured length
+    let ccm = Ccm::new(&aes, 12, 16).unwrap();
+    assert!(matches!(ccm.encrypt(&[0; 13], &[], b"data"), Err(AesError::InvalidNonceLength(13))));
+
+    // a 13-byte nonce leaves a 2-byte length field
+    let ccm = Ccm::new(&aes, 13, 16).unwrap();
+    assert!(ccm.encrypt(&[0; 13], &[], &vec![0; 0xffff]).is_ok());
+    assert!(matches!(ccm.encrypt(&[0; 13], &[], &vec![0; 0x10000]), Err(AesError::InvalidPlaintextLength(0x10000))));
+}
+
+#[test]
+fn long_associated_data() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    // the 0xfffe length encoding kicks in from 0xff00 bytes of aad
+    let ccm = Ccm::new(&aes, 7, 16).unwrap();
+    let aad = vec![0x5a; 0x10000];
+    let sealed = ccm.encrypt(&[1; 7], &aad, b"payload").unwrap();
+    assert_eq!(sealed, hex("95def9f2c76fa47c0994b894971c42625b69c655146da3"));
+    assert_eq!(ccm.decrypt(&[1; 7], &aad, &sealed).unwrap(), b"payload");
+}
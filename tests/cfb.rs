@@ -0,0 +1,92 @@
+// CFB known-answer tests from SP 800-38A Appendix F.3
+
+mod common;
+
+use aes::{Aes, Cfb, Segment};
+use common::{hex, hex16};
+
+static IV: &str = "000102030405060708090a0b0c0d0e0f";
+
+static PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51
+                          30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710";
+
+static KEYS: [&str; 3] = [
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+];
+
+// F.3.1, F.3.3, F.3.5: the first 16 bits of the plaintext
+static CFB1: [&str; 3] = ["68b3", "9359", "9029"];
+
+// F.3.7, F.3.9, F.3.11: the first 18 bytes of the plaintext
+static CFB8: [&str; 3] = [
+    "3b79424c9c0dd436bace9e0ed4586a4f32b9",
+    "cda2521ef0a905ca44cd057cbf0d47a0678a",
+    "dc1f1a8520a64db55fcc8ac554844e889700",
+];
+
+// F.3.13, F.3.15, F.3.17
+static CFB128: [&str; 3] = [
+    "3b3fd92eb72dad20333449f8e83cfb4a c8a64537a0b3a93fcde3cdad9f1ce58b
+     26751f67a3cbb140b1808cf187a4f4df c04b05357c5d1c0eeac4c66f9ff7f2e6",
+    "cdc80d6fddf18cab34c25909c99a4174 67ce7f7f81173621961a2b70171d3d7a
+     2e1e8a1dd59b88b1c8e60fed1efac4c9 c05f9f9ca9834fa042ae8fba584b09ff",
+    "dc7e84bfda79164b7ecd8486985d3860 39ffed143b28b1c832113c6331e5407b
+     df10132415e54b92a13ed0a8267ae2f9 75a385741ab9cef82031623d55b1e471",
+];
+
+fn check(segment: Segment, vectors: &[&str; 3]) {
+    for (key, ciphertext) in KEYS.iter().zip(vectors) {
+        let aes = Aes::new(&hex(key)).unwrap();
+        let expected = hex(ciphertext);
+
+        let mut data = hex(PLAINTEXT)[..expected.len()].to_vec();
+        Cfb::new(&aes, hex16(IV), segment).encrypt(&mut data);
+        assert_eq!(data, expected);
+
+        Cfb::new(&aes, hex16(IV), segment).decrypt(&mut data);
+        assert_eq!(data, hex(PLAINTEXT)[..expected.len()]);
+    }
+}
+
+#[test]
+fn cfb1_vectors() {
+    check(Segment::Bits1, &CFB1);
+}
+
+#[test]
+fn cfb8_vectors() {
+    check(Segment::Bits8, &CFB8);
+}
+
+#[test]
+fn cfb128_vectors() {
+    check(Segment::Bits128, &CFB128);
+}
+
+#[test]
+fn streaming_partial_segments() {
+    let aes = Aes::new(&hex(KEYS[0])).unwrap();
+
+    for segment in [Segment::Bits1, Segment::Bits8, Segment::Bits128] {
+        let mut expected = hex(PLAINTEXT);
+        Cfb::new(&aes, hex16(IV), segment).encrypt(&mut expected);
+
+        // uneven pieces that straddle block boundaries
+        let mut encryptor = Cfb::new(&aes, hex16(IV), segment);
+        let mut data = hex(PLAINTEXT);
+        let (a, rest) = data.split_at_mut(5);
+        let (b, c) = rest.split_at_mut(22);
+        encryptor.encrypt(a);
+        encryptor.encrypt(b);
+        encryptor.encrypt(c);
+        assert_eq!(data, expected);
+
+        let mut decryptor = Cfb::new(&aes, hex16(IV), segment);
+        for chunk in data.chunks_mut(7) {
+            decryptor.decrypt(chunk);
+        }
+        assert_eq!(data, hex(PLAINTEXT));
+    }
+}
@@ -0,0 +1,184 @@
+// Tests for the command line binary: key options, IV handling, key wrapping
+// and exit codes
+
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+
+/**
+    Feed input to a spawned binary and collect its output
+**/
+fn finish(mut child: Child, input: &[u8]) -> Output {
+    // usage errors exit before stdin is read, which closes the pipe early
+    if let Err(e) = child.stdin.take().unwrap().write_all(input) {
+        assert_eq!(e.kind(), ErrorKind::BrokenPipe, "{}", e);
+    }
+
+    child.wait_with_output().unwrap()
+}
+
+fn run(args: &[&str], input: &[u8]) -> Output {
+    let child = Command::new(env!("CARGO_BIN_EXE_aes"))
+        .args(args)
+        .env("AES_TEST_KEY_HEX", "000102030405060708090a0b0c0d0e0f")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    finish(child, input)
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aes-cli-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+// bytes to wrap, as if they were a key
+fn key_material(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 17) as u8).collect()
+}
+
+static IV: &str = "000102030405060708090a0b0c0d0e0f";
+
+#[test]
+fn key_file_is_raw_by_default() {
+    // also valid base64, but a raw file key means the bytes of the file
+    let key = "abcdefghijklmnopqrstuvwxyzABCDEF";
+    let path = temp_file("raw", key.as_bytes());
+
+    let positional = run(&["--iv", IV, "encrypt", key], b"message");
+    let file = run(&["--iv", IV, "encrypt", "--key-file", path.to_str().unwrap()], b"message");
+    assert!(positional.status.success());
+    assert_eq!(file.stdout, positional.stdout);
+
+    let base64 = run(&["--iv", IV, "encrypt", "--key-file", path.to_str().unwrap(), "--key-format", "base64"], b"message");
+    assert!(base64.status.success());
+    assert_ne!(base64.stdout, positional.stdout);
+}
+
+#[test]
+fn encoded_keys_agree() {
+    let path = temp_file("hex", b"000102030405060708090a0b0c0d0e0f\n");
+
+    let expected = run(&["--iv", IV, "encrypt", "--key-hex", "000102030405060708090a0b0c0d0e0f"], b"message").stdout;
+    for args in [
+        vec!["--key-base64", "AAECAwQFBgcICQoLDA0ODw=="],
+        vec!["--key-file", path.to_str().unwrap(), "--key-format", "hex"],
+        vec!["--key-env", "AES_TEST_KEY_HEX", "--key-format", "hex"],
+    ] {
+        let mut full = vec!["--iv", IV, "encrypt"];
+        full.extend(args);
+        assert_eq!(run(&full, b"message").stdout, expected, "{:?}", full);
+    }
+}
+
+#[test]
+fn key_errors_have_their_own_exit_codes() {
+    let code = |args: &[&str]| run(args, b"message").status.code().unwrap();
+
+    assert_eq!(code(&["encrypt", "--key-hex", "0011"]), 3);
+    assert_eq!(code(&["encrypt", "--key-hex", "zz"]), 9);
+    assert_eq!(code(&["encrypt", "--key-base64", "AAECAwQFBgcICQoLDA0ODx=="]), 9);
+    assert_eq!(code(&["encrypt", "--key-env", "AES_TEST_UNSET_VARIABLE"]), 2);
+
+    // newline at the end makes the raw key 33 bytes
+    let path = temp_file("newline", b"000102030405060708090a0b0c0d0e0f\n");
+    assert_eq!(code(&["encrypt", "--key-file", path.to_str().unwrap()]), 3);
+
+    // usage errors
+    assert_eq!(code(&["encrypt", "--key-hex", "00", "--key-format", "hex"]), 2);
+    assert_eq!(code(&["encrypt", "--key-env", "X", "--key-format", "utf16"]), 2);
+}
+
+#[test]
+fn password_mode() {
+    let run_with = |password: &str, args: &[&str], input: &[u8]| {
+        let child = Command::new(env!("CARGO_BIN_EXE_aes"))
+            .args(args)
+            .env("AES_TEST_PASSWORD", password)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        finish(child, input)
+    };
+
+    let encrypted = run_with("hunter2", &["encrypt", "--password-env", "AES_TEST_PASSWORD", "--iterations", "1000"], b"message");
+    assert!(encrypted.status.success());
+    assert_eq!(&encrypted.stdout[..8], b"AESPWD01");
+    assert_eq!(encrypted.stdout[8..12], 1000u32.to_be_bytes());
+
+    let decrypted = run_with("hunter2", &["decrypt", "--password-env", "AES_TEST_PASSWORD"], &encrypted.stdout);
+    assert_eq!(decrypted.stdout, b"message");
+
+    let code = |password: &str, args: &[&str], input: &[u8]| run_with(password, args, input).status.code().unwrap();
+
+    // an iteration count above the limit is refused before any work is done
+    let mut crafted = encrypted.stdout.clone();
+    crafted[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert_eq!(code("hunter2", &["decrypt", "--password-env", "AES_TEST_PASSWORD"], &crafted), 8);
+    assert_eq!(code("hunter2", &["encrypt", "--password-env", "AES_TEST_PASSWORD", "--iterations", "0"], b""), 8);
+
+    assert_eq!(code("", &["encrypt", "--password-env", "AES_TEST_PASSWORD"], b"message"), 10);
+    assert_eq!(code("hunter2", &["decrypt", "--password-env", "AES_TEST_PASSWORD"], &[0; 64]), 12);
+
+    // --iterations without a password, and a password for wrap, are usage errors
+    assert_eq!(code("", &["encrypt", "--key-hex", IV, "--iterations", "1000"], b"message"), 2);
+    assert_eq!(code("hunter2", &["wrap", "--password-env", "AES_TEST_PASSWORD"], b"message"), 2);
+}
+
+#[test]
+fn random_iv() {
+    let key = ["--key-hex", "000102030405060708090a0b0c0d0e0f"];
+    let with = |command: &str, input: &[u8]| {
+        let mut args = vec![command];
+        args.extend(key);
+        run(&args, input)
+    };
+
+    // the IV is written in front of the ciphertext and differs every time
+    let first = with("encrypt", b"message");
+    let second = with("encrypt", b"message");
+    assert!(first.status.success());
+    assert_eq!(first.stdout.len(), 32);
+    assert_ne!(first.stdout[..16], second.stdout[..16]);
+    assert_ne!(first.stdout[16..], second.stdout[16..]);
+
+    // the rest is the ciphertext under that IV
+    let iv: String = first.stdout[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    let mut args = vec!["--iv", &iv, "encrypt"];
+    args.extend(key);
+    assert_eq!(run(&args, b"message").stdout, first.stdout[16..]);
+
+    // decrypt reads it back
+    assert_eq!(with("decrypt", &first.stdout).stdout, b"message");
+    assert_eq!(with("decrypt", &second.stdout).stdout, b"message");
+
+    // input too short to hold the IV
+    assert_eq!(with("decrypt", &first.stdout[..15]).status.code(), Some(4));
+    assert_eq!(with("decrypt", b"").status.code(), Some(4));
+}
+
+#[test]
+fn wrap_and_unwrap() {
+    let material = key_material(32);
+    let wrapped = run(&["wrap", "--key-hex", IV], &material);
+    assert!(wrapped.status.success());
+    assert_eq!(wrapped.stdout.len(), 40);
+
+    let unwrapped = run(&["unwrap", "--key-hex", IV], &wrapped.stdout);
+    assert!(unwrapped.status.success());
+    assert_eq!(unwrapped.stdout, material);
+
+    // wrong key fails the integrity check
+    let other = "0f0e0d0c0b0a09080706050403020100";
+    assert_eq!(run(&["unwrap", "--key-hex", other], &wrapped.stdout).status.code(), Some(6));
+
+    // wrapping has no IV and no padding choice
+    assert_eq!(run(&["--iv", IV, "wrap", "--key-hex", IV], &material).status.code(), Some(2));
+    assert_eq!(run(&["--padding", "none", "unwrap", "--key-hex", IV], &wrapped.stdout).status.code(), Some(2));
+}
@@ -0,0 +1,73 @@
+// CMAC tests with the RFC 4493 and RFC 4615 vectors
+
+mod common;
+
+use aes::{cmac, Aes128, AesError, Cmac};
+use common::{hex, hex16};
+
+static KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+
+static MESSAGE: &str = "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51
+                        30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710";
+
+// RFC 4493 section 4: message length and tag
+static RFC4493: [(usize, &str); 4] = [
+    (0, "bb1d6929e95937287fa37d129b756746"),
+    (16, "070a16b46b4d4144f79bdd9dd04a287c"),
+    (40, "dfa66747de9ae63030ca32611497c827"),
+    (64, "51f0bebf7e3b9d92fc49741779363cfe"),
+];
+
+// RFC 4615 section 4: key and PRF output for the message 00..13
+static RFC4615: [(&str, &str); 3] = [
+    ("000102030405060708090a0b0c0d0e0fedcb", "84a348a4a45d235babfffc0d2b4da09a"),
+    ("000102030405060708090a0b0c0d0e0f", "980ae87b5f4c9c5214f5b6a8455e4c2d"),
+    ("00010203040506070809", "290d9e112edb09ee141fcf64c0b72f3d"),
+];
+
+#[test]
+fn rfc4493_vectors() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    for (len, tag) in RFC4493 {
+        let message = &hex(MESSAGE)[..len];
+
+        assert_eq!(cmac::cmac(&aes, message).to_vec(), hex(tag));
+
+        // the same tag when fed in uneven pieces
+        let mut mac = Cmac::new(&aes);
+        for chunk in message.chunks(7) {
+            mac.update(chunk);
+        }
+        assert_eq!(mac.finalize().to_vec(), hex(tag));
+        assert!(mac.verify(&hex(tag)).is_ok());
+    }
+}
+
+#[test]
+fn truncated_tags() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    let (len, tag) = RFC4493[2];
+    let mut mac = Cmac::new(&aes);
+    mac.update(&hex(MESSAGE)[..len]);
+
+    assert_eq!(mac.finalize_truncated(12).unwrap(), hex(tag)[..12]);
+    assert!(mac.verify(&hex(tag)[..8]).is_ok());
+
+    assert!(matches!(mac.finalize_truncated(4), Err(AesError::InvalidTagLength(4))));
+    assert!(matches!(mac.verify(&hex(tag)[..4]), Err(AesError::InvalidTagLength(4))));
+
+    let mut forged = hex(tag);
+    forged[15] ^= 1;
+    assert!(matches!(mac.verify(&forged), Err(AesError::AuthenticationFailed)));
+}
+
+#[test]
+fn rfc4615_vectors() {
+    let message: Vec<u8> = (0..20).collect();
+
+    for (key, output) in RFC4615 {
+        assert_eq!(cmac::prf_128(&hex(key), &message).to_vec(), hex(output));
+    }
+}
@@ -0,0 +1,20 @@
+#![allow(dead_code)]
+
+/**
+    Decode a hex string, whitespace is ignored
+**/
+pub fn hex(s: &str) -> Vec<u8> {
+    let digits: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect()
+}
+
+/**
+    Decode a hex string of exactly one block
+**/
+pub fn hex16(s: &str) -> [u8; 16] {
+    hex(s).try_into().unwrap()
+}
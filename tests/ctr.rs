@@ -0,0 +1,107 @@
+// CTR tests from SP 800-38A Appendix F.5 and RFC 3686
+
+mod common;
+
+use aes::{Aes, Aes128, AesError, CounterLayout, Ctr};
+use common::{hex, hex16};
+
+static PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51
+                          30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710";
+
+static INITIAL_COUNTER: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+
+static VECTORS: [(&str, &str); 3] = [
+    // F.5.1/F.5.2 CTR-AES128
+    ("2b7e151628aed2a6abf7158809cf4f3c",
+     "874d6191b620e3261bef6864990db6ce 9806f66b7970fdff8617187bb9fffdff
+      5ae4df3edbd5d35e5b4f09020db03eab 1e031dda2fbe03d1792170a0f3009cee"),
+    // F.5.3/F.5.4 CTR-AES192
+    ("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+     "1abc932417521ca24f2b0459fe7e6e0b 090339ec0aa6faefd5ccc2c6f4ce8e94
+      1e36b26bd1ebc670d1bd1d665620abf7 4f78a7f6d29809585a97daec58c6b050"),
+    // F.5.5/F.5.6 CTR-AES256
+    ("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+     "601ec313775789a5b7a7f504bbf3d228 f443e3ca4d62b59aca84e990cacaf5c5
+      2b0930daa23de94ce87017ba2d84988d dfc9c58db67aada613c2dd08457941a6"),
+];
+
+#[test]
+fn sp800_38a_vectors() {
+    for (key, ciphertext) in VECTORS {
+        let aes = Aes::new(&hex(key)).unwrap();
+
+        let mut data = hex(PLAINTEXT);
+        let mut ctr = Ctr::new(&aes, hex16(INITIAL_COUNTER), CounterLayout::Counter128);
+        ctr.apply_keystream(&mut data).unwrap();
+        assert_eq!(data, hex(ciphertext));
+
+        let mut ctr = Ctr::new(&aes, hex16(INITIAL_COUNTER), CounterLayout::Counter128);
+        ctr.apply_keystream(&mut data).unwrap();
+        assert_eq!(data, hex(PLAINTEXT));
+    }
+}
+
+#[test]
+fn rfc3686_vectors() {
+    // test vectors #1 and #2: nonce || iv || 32-bit counter starting at 1
+    let cases = [
+        ("ae6852f8121067cc4bf7a5765577f39e", "00000030000000000000000000000001",
+         "53696e676c6520626c6f636b206d7367", "e4095d4fb7a7b3792d6175a3261311b8"),
+        ("7e24067817fae0d743d6ce1f32539163", "006cb6dbc0543b59da48d90b00000001",
+         "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+         "5104a106168a72d9790d41ee8edad388eb2e1efc46da57c8fce630df9141be28"),
+    ];
+
+    for (key, counter, plaintext, ciphertext) in cases {
+        let aes = Aes::new(&hex(key)).unwrap();
+        let mut data = hex(plaintext);
+        Ctr::new(aes, hex16(counter), CounterLayout::Nonce96Counter32).apply_keystream(&mut data).unwrap();
+        assert_eq!(data, hex(ciphertext));
+    }
+}
+
+#[test]
+fn seek_and_partial_writes() {
+    let aes = Aes::new(&hex(VECTORS[0].0)).unwrap();
+    let ciphertext = hex(VECTORS[0].1);
+
+    // decrypt an unaligned range in the middle
+    let mut ctr = Ctr::new(&aes, hex16(INITIAL_COUNTER), CounterLayout::Counter128);
+    ctr.seek(21);
+    let mut data = ciphertext[21..50].to_vec();
+    ctr.apply_keystream(&mut data).unwrap();
+    assert_eq!(data, hex(PLAINTEXT)[21..50]);
+    assert_eq!(ctr.position(), 50);
+
+    // byte-at-a-time gives the same keystream
+    let mut ctr = Ctr::new(&aes, hex16(INITIAL_COUNTER), CounterLayout::Counter128);
+    let mut data = hex(PLAINTEXT);
+    for chunk in data.chunks_mut(3) {
+        ctr.apply_keystream(chunk).unwrap();
+    }
+    assert_eq!(data, ciphertext);
+}
+
+#[test]
+fn counter_overflow() {
+    let aes = Aes128::new(&[0; 16]);
+    let initial = hex16("000000000000000000000000fffffffe");
+
+    // two blocks fit before the 32-bit counter wraps
+    let mut ctr = Ctr::new(&aes, initial, CounterLayout::Nonce96Counter32);
+    let mut data = [0u8; 32];
+    ctr.apply_keystream(&mut data).unwrap();
+
+    let mut data = [0u8; 1];
+    assert!(matches!(ctr.apply_keystream(&mut data), Err(AesError::CounterOverflow)));
+    assert_eq!(data, [0]);
+
+    // the nonce is never carried into
+    let mut ctr = Ctr::new(&aes, initial, CounterLayout::Nonce64Counter64);
+    let mut data = [0u8; 33];
+    ctr.apply_keystream(&mut data).unwrap();
+
+    let mut ctr = Ctr::new(&aes, [0xff; 16], CounterLayout::Counter128);
+    ctr.seek(16);
+    assert!(matches!(ctr.apply_keystream(&mut [0u8; 1]), Err(AesError::CounterOverflow)));
+}
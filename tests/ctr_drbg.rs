@@ -0,0 +1,607 @@
+// CTR_DRBG tests following the CAVP drbgvectors files: instantiate, an
+// optional reseed, two generate calls of 512 bits and the second output.
+// Prediction resistance cases reseed before each generate call. CAVP_VECTORS
+// are taken from the CAVP files, VECTORS were generated with OpenSSL's
+// CTR-DRBG fed through its TEST-RAND entropy source.
+
+mod common;
+
+use aes::{encrypt, AesError, CtrDrbg};
+use common::hex;
+
+struct Vector {
+    key_len: usize,
+    derivation_function: bool,
+    entropy: &'static str,
+    nonce: &'static str,
+    personalization: &'static str,
+    entropy_reseed: &'static str,
+    additional_reseed: &'static str,
+    entropy_pr: [&'static str; 2],
+    additional: [&'static str; 2],
+    returned: &'static str,
+}
+
+// drbgvectors_no_reseed CTR_DRBG.rsp, PredictionResistance = False, COUNT = 0
+static CAVP_VECTORS: [Vector; 5] = [
+    // AES-128 use df
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "890eb067acf7382eff80b0c73bc872c6",
+        nonce: "aad471ef3ef1d203",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "a5514ed7095f64f3d0d3a5760394ab42062f373a25072a6ea6bcfd8489e94af6cf18659fea22ed1ca0a9e33f718b115ee536b12809c31b72b08ddd8be1910fa3",
+    },
+    // AES-128 no df
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "ce50f33da5d4c1d3d4004eb35244b7f2cd7f2e5076fbf6780a7ff634b249a5fc",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "6545c0529d372443b392ceb3ae3a99a30f963eaf313280f1d1a1e87f9db373d361e75d18018266499cccd64d9bbb8de0185f213383080faddec46bae1f784e5a",
+    },
+    // AES-192 no df
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "f1ef7eb311c850e189be229df7e6d68f1795aa8e21d93504e75abe78f041395873540386812a9a2a",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "6bb0aa5b4b97ee83765736ad0e9068dfef0ccfc93b71c1d3425302ef7ba4635ffc09981d262177e208a7ec90a557b6d76112d56c40893892c3034835036d7a69",
+    },
+    // AES-256 use df
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14",
+        nonce: "496f25b0f1301b4f501be30380a137eb",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d",
+    },
+    // AES-256 no df
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "df5d73faa468649edda33b5cca79b0b05600419ccb7a879ddfec9db32ee494e5531b51de16a30f769262474c73bec010",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "d1c07cd95af8a7f11012c84ce48bb8cb87189e99d40fccb1771c619bdf82ab2280b1dc2f2581f39164f7ac0c510494b3a43c41b7db17514c87b107ae793e01c5",
+    },
+];
+
+static VECTORS: [Vector; 36] = [
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "34b6177ba893964fa2e25ace7393e3f8",
+        nonce: "d14e437d455e138b",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "b827f5dc4455d5695869c02eeec50a045f2cd8d0a2c482d315c510bc03921ad54ffc9aa759022ed11427982206d5954a38e9cbd5dbc12dbe8d22e952462c5fb9",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "bb240afaada2f3fdd8615f75cf1b218b",
+        nonce: "c5862e0dad13061f",
+        personalization: "8149edc5361f1aa96c130200153ea629",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["fccaf807cf8c6f81c8efbdfdc4107a52", "bffe569246d82d9a5f4f70e851aadff1"],
+        returned: "78ece4d32e48fe2ea1a29b841a9481fd01457df47ce4119ab3a157fc7f828c5b0f3fac6f15d2d06754a2cf23e7337d7cf573276e848a91fc5d662e7eca8e87f6",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "88926c7ff688e2f20e7f578eb07f6ded",
+        nonce: "f58bb92eeed8ad42",
+        personalization: "",
+        entropy_reseed: "314d0e95677a291d8e27c48f8fe6b4e5",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "20e8df169492e24d9d78bed3da4de2736960ec584563fad96203a2db99b03fa644b5baadb418b3d708060ed62a76088b69e5748f78939d9a223e355fd7325ef8",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "20c7fe39fe8e4dacad34af2da2594945",
+        nonce: "611e5b0d4d7fdfa0",
+        personalization: "b0f3a39a016a1f907daebdfc6521ca05",
+        entropy_reseed: "5dfbda7bbca02bcf6166350c662a7770",
+        additional_reseed: "dccf0f74989a623aaaa5db15416a781c",
+        entropy_pr: ["", ""],
+        additional: ["27cab968f193d69db3811560509960e3", "03144d8cf5c46d19c4ba310a278b1316"],
+        returned: "d7f98651d5592849ee338219bc5e8c04deef16975d26930f1bc90e3be2ca47ad727e9d20263d429b92745da10bd3562d3431b263f58f69a2749aa2451dc14a9b",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "f13ca35b7c6850989424a7197ba542e3",
+        nonce: "64ee208a5728b3d9",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["e8f059645d2ea8dad99842bef1840ec8", "cc3b2907834f35feb9102cb082e12170"],
+        additional: ["", ""],
+        returned: "55f385dafc46401b574094beec13abcf4dab7e9e4fe0a98d60464ccaaaeed3651f443f8a90df0f4ecd558b89e2b4800901aaa608d9937d4dadf722636e6a2866",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: true,
+        entropy: "3a4e564c7f376e62d35e2a229e990316",
+        nonce: "7a418059d4a4518f",
+        personalization: "3f860bcec09edb6d44307f967a005155",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["c245d7119dfe005cc2d5b781aef58fb4", "f826c541a03527c44735e67c635b33b8"],
+        additional: ["e1607ad450b4b296c1fbc220dad30d4d", "67481ef8687c90e5fbfbd1678d15abee"],
+        returned: "b28d9773fdd657fe723d96c03c76cf3adc029aa4cf65006dc0a425d2e56891db89576dce1f10512388fa782552d2e57c3ac9e3bad1aa8ea221a7763039f3cf93",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "9adb3ac4a6402fd324bc38a3891ae8450803e64a196a920cb89d4fc0150cf445",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "1574be498151701dc80a2cb01d31bd14b12e26a20ad11d01452675e38b86329fce6fa48fbd327bdb0127712eb0150cf03bcd33756fb1db8b84800d778e6d879d",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "e44a3a7c3c57615d062e9ab3f256a6a5cdad858c00e9da0161e13f6212477e79",
+        nonce: "",
+        personalization: "2061ad91f104174117e804a7708d7ad2b7b4e32b4341dd78c0433eefaa55c24c",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["70119b544c89fd9009798b66cab6f2ef208cdee6be3f10f769ab0e9155e0fe91", "8e9d5bedf45bfe08382ca99eae7feb0008d9ca2950ca73455c16860c62411bba"],
+        returned: "940e639606763b798a6b8ae3383fdb83d44fd771681bdaf78b52b0295de7bedb96cefb1d4834d2ad45bcc32c40ad1713635030392c3a479ef97799f31aebb4de",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "506b8b74b00bc106d9e1b12e3106d8aff2c4b1667e4d17f461f5e13a3f19dcbb",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "bbe69e9f2246d19f7aad063351f39ce8584357857d9c10e8b827bbd1dc30eb8a",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "b5c718df18835984b58249c88cef7d4ae330ec975b7d9e65283d770e03d9c0d81fb31d09694b6cb61c851d58cf74acb0c4a7834732db9baa2113706f2f003522",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "b1a73569e4709e3fce8838f357460fe05cb94ee3a4a332ccdf8ba9078efc0c5c",
+        nonce: "",
+        personalization: "431b8ba708b1147a381ff8553f487cb632efcf640cf8bd5f336e16b8a6ae8901",
+        entropy_reseed: "5c2ca3b8499869574dfe19d1eac3f0c5087451fe5397ffc8e12be3cdc31d4f18",
+        additional_reseed: "2e7ad3f32ed96e458b812dcdfe584c5ab5ec205de1dcc2f40e595b8409ee669e",
+        entropy_pr: ["", ""],
+        additional: ["764afd20d1ef4a18bdfccea227f94767fbd65ddc62bd83889e4d9d6e440cca81", "4c2cdae735cd03a3c906b71415a8d81791cc13a362b922114864b274b26e8ca1"],
+        returned: "dc3988b8cd4ea6ad94a4730597d190cfbbf267de32e90c701552474cc34c3edad04cc7bf0f5eaebad74d3fd6c3fe9549425285f594b0349906c6c42b1bc61633",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "0587c333ffb52adc21b0cb1017bd66de7243f41ecb13267a475fd31d2ae7cf74",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["53db99fc7993e9062ce89ece98e0ee0b793741d5d76a4b2535a9725490307d62", "f9f10f5364fcf4508573f960293f67f6eb3799d60be95a005eab601c3b7e5c1f"],
+        additional: ["", ""],
+        returned: "cfcbcd9e8493b763668eeed492f29a005f184f4743330179d004dd6403c1b4f90aa3b0aa10d772e97b20aea16fcd17af8024ddeb20d116a3b3ef51fd68323818",
+    },
+    Vector {
+        key_len: 16,
+        derivation_function: false,
+        entropy: "51b94ae317a224423738bccafa0bfa399d6a2e1062a1b64cb52289833df35c28",
+        nonce: "",
+        personalization: "61d8bc3dd59650e4473d327175292a667ef440408a138f8860585da465d4dcb6",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["795dcfbe6619946b007c3143468e25288dcfa4ab8e696f07e9e1e98e9c172564", "16c789cb3a3edf6a0d9c1e7f9a6f9a32a0ec539330f70335d375605e6b52d843"],
+        additional: ["b1fa5ee08809abe457f8bf4176a5b8da7bca873e630ce6f8a7ba38a8e0d7d75e", "69661aae3843786671b63c30fe7d2d268d020d7e3b6ebe4847c2be89d1180f0d"],
+        returned: "b361d6e7b5d1f20da5e5697cf8e72738a8f0cbfaae680318e0efa643a0142b4d8e7232b2324b49012395db2e5f1028501a54dabc3fb144480d6b104afd98333e",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "eac7168243d287104ba2767b0883d9d571b4503084fa0d1c",
+        nonce: "10a661406f62cc38ad8f00c3",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "7b7e21a7961a7bcc73c9560e292168e18e7b0cf0a49d54fd2f5186acbe45d88aa9ad2ffc7a619670752cb2224063b0c83c9e8e7f0aa2be6210c6ba9c6e832eb1",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "55ab4abb9b7361ace28ad61b0e928f96ea44cee1882a6e64",
+        nonce: "0a6439762bbecf8114bfdbeb",
+        personalization: "e9c5fbb1d711a450d51d28d6ad78c24238443475eec861bd",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["3ccbae735ea51aa64936aa9aa3d6138a84648d7b4c771534", "d4e80551e74ad94aff6db47f97fff117bb8b11b6b5c59614"],
+        returned: "dcb453af237fdfe0f11e8fb0f857dbb02b0bcbba56283ba10c201ff7c412bbbd6b531fb360af8a22a9261898592aea06deca7ccd8a00ba43ffaac3c65949164c",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "d160ff31dcae32c52268e63c85e264edc1fe4e2024a6e52d",
+        nonce: "3d17b98fd350e642ef4f2abf",
+        personalization: "",
+        entropy_reseed: "3669ead908885bd9a786a257a7a2fb1a42e4521a93da57c4",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "d9861c089059bc695a2fe7be8906e3f70ea9125d246de7bf579d01ab44317da2dc84ada979ace7f02f24eab78de49c316c429fac8ea1029315a54b5be292310b",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "836a5aa84854b0085d36826fe37d55be54c5acc48ecbde63",
+        nonce: "de31d49b424feca6ccf22e04",
+        personalization: "290f65678b336ed02d5f73ae748d1089e2637567606a981c",
+        entropy_reseed: "db96ffaaf311fcc8fd9ddab5689c50cbb78264df059dd68d",
+        additional_reseed: "5471b994b65c00f92748abc65d089c55e6443f60ed16f9fd",
+        entropy_pr: ["", ""],
+        additional: ["db22c9f7f73c46692713be0455975d485ce01a0e6766e88d", "6f8b5a5e439ae3b39d42d10434dbd68d1e6a717b97e54215"],
+        returned: "dcc202b65abfbd8f3a6b5297c32bb5a56871aedf377cc95c0b4ab7f6235a16a45037ffd93e830d8167c0e8821fb95222e740ae9957884ed106d241bc76e473d4",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "e8e35a94e6758a0ae8965abb1fc4b9a6348f2d2cda5c9ffa",
+        nonce: "b216e04badd372ae075d161e",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["9fab4732f530e57a3aa899bace57ddd3d654621d0236c8cf", "51e2fb75b516fa7da01228faf629bd7dfffbbdea5dbfb048"],
+        additional: ["", ""],
+        returned: "711ae4a88f98a1a37e1dee473214cb6acbb874118878e0a7fafe70c09860f13270143b7ab507a431b5235863ecf87144902b4685944e3e63dd302bbd6bab89e7",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: true,
+        entropy: "b005911dbf7f758eb60adf4aed2b9af1da74520065ace743",
+        nonce: "22a7e8901dd0600894297899",
+        personalization: "0a6973dc802ef3ab9884533397b0b65eec5440f3ed12fd3a",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["aa9a2c5ff91fc6cbcf200a14bfb3103631a7579b1b64d630", "80508fcc4386dd1a8f097c76381b9f29914eeda926193cc8"],
+        additional: ["c69546f870bdb05b25879ea1ce6b7beec50d09fabdd6f153", "2855518ef476bdd8d62a147f34428eba80565f2c46945388"],
+        returned: "e2e9fe6d6f71351819c7ad0027f8ae0b2ff1945adbff4289e53fab7a7393894916bb68dc78da221899daa34c43be774948d4e98961f0159bf9095e7a52f96c01",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "89c81abd9b917ba1d1b507b94a98e5d282db4c0e9370ed1a54b19ae4e995067d0053fc2cfdd7ab28",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "f33747033e5e316e6393a742e4455d9bc7ad59c92b8c03331dafee875d73700e40e510853738986ad5f1cb7d075fc94d46fb58fea1b957b4c0d040452146fc99",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "90b3dce29cbcedc013f69459f1a25f19cf92f19818f810fbde6dd889d0ed878f426d6d171efc477c",
+        nonce: "",
+        personalization: "117c74a24a4ec0356cd0e229f5ec0dad0ff95218f5f338181e12c6dc885cb35a1f5f223ceff70f66",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["3e6a4b94d6f94a96074ecb734d4b2520cb2c12bd5d27a2046a08d9aa99198b79e266bd44ae2af526", "bbd9d9b92480ef97645e6a823fb6d68756288ab2a53dd7e3c285258a2a3963986046264496828ef6"],
+        returned: "6527ac5d9882999b9756b0e2778c2fb53b949a631a013c3865f2e2490accef1f29d16dc033620345b07b30540c1ed3c1afd9bf0fa8184ce9e0eb25b5aa675aad",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "d30d1eb123d0322b3b6f3ad3a94de59fbd5155a89ab967277e39f219ea7cc2fef0ea02ce17b0275c",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "63c54bbbd748d4b3dc69c8ce8e4ba7edae66024c4c08d86bdf02c2991950ed84b3248bb11bdda5ac",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "bdcf47fde452e3b6d788652c7598766d01fda5e36a23064e3a3a856d48ae6d0584ace5b478539ca1f2549847e526abc86a822023856c2fc6b79cee7ca4f3df94",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "d4b9bee4fa7ddfe773f4045cab39adf41e3483cf1231976807f8e3e5eddd5c2340125a296a1f3938",
+        nonce: "",
+        personalization: "9c5ac91e3737a6adb94b5ce89990166fce2cea7be20df15a0914e3868df9f58dcafc1c29ce285b85",
+        entropy_reseed: "77bca1f1c40dcc06536e52087515c69804dd24c849ff20bc07bb39ef7a034b65d29c566b302ec9d9",
+        additional_reseed: "ee62be8baa5a5d7c0455c7c4ebcdfe2fbaff8ba5a9166ca2f16f0da6223f59afd6381d6064579875",
+        entropy_pr: ["", ""],
+        additional: ["c3bd9d09f0779be904ca6d3dbb28981a04db6d7967ef5447ab657ac4e5eb5c5e3fc200d7cefcd4c9", "ca782558720677fa31eaf2c30b6b464dec3a4e71e507c1e179128cf7bf97ccef72ae42ae06fd03c1"],
+        returned: "f773456893c27e75cd64cb35062698aa31133e4e0f6f32e99212b701658e6a0bd441fd30bab4f626424a41445628aeb3a5b2d14b45ab52799e6727e0c9e938f4",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "5d5537daa8de13358a077c520196fb47b450e9cc64180d8a4dcda2244dd8689c564b35ef1dce0252",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["56abea9f812e6883e0f304b9ca2c9c6df2ed3d4dd5e57bbd5e641788e98c37caef4468c9d954f6cd", "a133081affdcde35a088fd41ba4499cd33ce4d354e212e48b2469dcabe48fef2134fad20b54e34b1"],
+        additional: ["", ""],
+        returned: "3a997b8a3cb14ddda87bd1e5608dd5cd63b3b144e64e06d388ed71cc74a3ff92b9a44ce606be5fae5ceaab4f17f74c70509e0d25b32ba3895fb07e12bd9d468e",
+    },
+    Vector {
+        key_len: 24,
+        derivation_function: false,
+        entropy: "a3b060172753f06d66f6daebb0091951ee1cc84debffd8e40fee8a64209ab7acfc85f19638e1bea9",
+        nonce: "",
+        personalization: "fb0276eb6f7a2187dc3f9a3cbc3258be21874e98e524bcca120daa762a0e2bff0ba46d03d36fa527",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["abee38b44d3effb34cb3d6e14a0811d8ebf76e1eac1d217f9084f88cf3adc436e992de38ee7dedbb", "fd7f2149194f01d4d4c8312f3d5c04f7a855107e74a40c80a8be15553778c9e0084794dabe1f9b73"],
+        additional: ["2eb52a786d6f2443192e8f0e70880ea736712921be2dba425902db6a002a88c9d8580d04ef4a338f", "6872f9b5f9f38278616fdde64af70ba274e8ca3bf031a867fc2f1693bf98eabed191bab04d6aeac4"],
+        returned: "6ccd8873097046c8e4f48c73e10017777570c40d73060b68c8271b52558d2790ec906dec01c8d13643c7e0447787176c21eff67ad33dd276ea5d40499b9b770e",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "0de554daf5cd3444b7cecd9f6426c6d9f78804bf5417dbeca17b6c9d2fe4f907",
+        nonce: "04456510252b42cd0bc78dffb3214128",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "8931db0e6381ccdead05275d6a6d0a1ff7c53a395660f9794888de55439be7b3f3132e1fe01f34c80df98c397cb340c3d0ac404feed6912cfade30ca77a20c13",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "adc35d95c4e9719738415d34caf7f0ec28a2fecd5706f15596d8da1a5aaa369e",
+        nonce: "8cbad23374cf6114df25a0e43fb955c4",
+        personalization: "c1ae406e325ffa770ba48572d9354df5cbbcd4a712a810958a8229e5bc6c04c7",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["4e309a32bc3c5a37682c338793571ddc6e91eebdc234febd64a1541bf4eb135f", "345519eabbbf0f9cb3105f61c42f67e1dc8c623d6b8d7ba548873c046fdafb56"],
+        returned: "3b7b28626cfbdc7a090de28ee694354cd01077075692ba8c2875129fb0af14f2f573ebc0c1215d6e5cca2687076367c4af846d5ac66367254a5e4bcf255dce55",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "22331b7c4603b3a623a79224f7bfb41f80e709b1a5947630b9fc64318ce3379e",
+        nonce: "97e1367577d91b65f7ef5649ab40306e",
+        personalization: "",
+        entropy_reseed: "0baa1d82e38f9fa9d3a5ae8d0a7ad817ef7e889014de57f238fd3f365d254dd5",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "438e86747b533fdc05bd2659c80e270d339ca5286b543127825af97d180d38347434e92972dc24b4deeeb4c756182a64fba5b21e8cb047a15eebd87b61a2250d",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "95852045bde6b909376224332daf1ada10ad30567531e199a7c656406411055b",
+        nonce: "c8552b38538fbf35c87eec21fdb05d06",
+        personalization: "f1df1da7f58e7585f74b33e8596ad0800e27c89846e20ff36890a3ba9f8a2cd5",
+        entropy_reseed: "b04486e6b115ebd5e17f93ec8a56bc1ba01838f6a1756e30277754bd6cea667a",
+        additional_reseed: "153ff5db11ab18c872b83495af450e0e1652203611d1f06116b9b9635fc34602",
+        entropy_pr: ["", ""],
+        additional: ["16c5c6b9715e07853fa45a89faefde38c7a3873e101f557c90fe2281cbeb5442", "ad614c2027c829633e651be249e2c333ab9b32d1a08f31e5d270871432a22ae1"],
+        returned: "61df1d62f23f14419ab06d362e4173da35306de23427247d305724311001e1a7dbf401d011258337f08a99af10a2bfdc95af3073d20fd487429ae14f320e1d61",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "136c8f697ba02569119f891b93fc02e5f3acb6363eb18ec7208e71c3498b9772",
+        nonce: "60c0a9df3fb3bce947b599ec31803410",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["c09c7e330e76a20dd703dba7bf4a238e55b1ea298750413dba27b3695baf4872", "0c0e89a3ca35769650ded1e594d5a9f2a94171e42e4fb2639500196a7008951a"],
+        additional: ["", ""],
+        returned: "a41b50eee37ad5f0d3041e87ca164e0dccf3ff280bf298089f314e4d456563d12e4a62cc37c14eff023d5dd1fc006e1558d47b72414d79937ae823c3d482e0bb",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: true,
+        entropy: "8638b808f7c9031ca4f70d62bfa608daabb6734a8d6739bc1fa4afd5ee14a56c",
+        nonce: "fecda5deabe019a5fafe37cd20296d23",
+        personalization: "46170fcc943e71c8e79d6235e44923be9b9c471370076d6c3b3ada1b98a8db2d",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["a3bce164ba66a5289f6ec148a55c5080cb5855dbb7e5cd1b5fd1f7423b1355ea", "fdb86b270b64fff718851e817f7aed1ded25172eb256fa65d588e67db60a0b78"],
+        additional: ["b75d8a2681f61cb941d8eb5faaf5d8d99fdd96618994dce3c0585793098ef7ea", "835ed7fe82e18139f56dc2d690252bb08e8aab35a6a0ad4892ef82b1f26905e4"],
+        returned: "09c6ea85d51f2ded0a2ca8cfa74e06e7d190ed0e4f4cf299d328954c35738f65893b515d01b671b9744bc9bedc0ca11738567bc52f5543705b5334983b3235c5",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "9fc11ee9e06ea29f9674d7a29adcfb17773074a692b9d7185522de10c65e0f4394101146317e5a5b3351c20b8f49553c",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "a015397a38567e03385a5deaf61a77d076798e97d405c64af15b3fa8d3110158413355115f0e43c1c2ada94e07a1bbff486f7e617b48c4b678adbb983e49b8e3",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "fb336e16fc3656056604b32a9934d71e1368abc97a315bc7a531d95dd8cec109a74913eac30d1b395b832410c84c07e1",
+        nonce: "",
+        personalization: "1b1ea9d95eaf9969165d6a85b75680ad688afac235e87ba05ed1c044f72b818c3f3bfebc2bc3c191db26dc99516ec42e",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["78f80eefd21bf52319adcd32606cea62398be6e830da3071d8e02d4f4e00b3d9e3d4f511db9cbd00d04b54514dfe017a", "fb497adba73616ba90797bd6a5d5d6bb1217b53c35bd4cce9d64ad76e5650a7f37ae5540df5431d82d23c6213e806950"],
+        returned: "0d1098d79e58404c3b805f4ef552220730826f426249f599751551d2481c8c1cb95a48e9887d56d6de06ca547caea2ba3d3a9b63b24361907d718f4c630f4dd8",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "03c386e19ad30e9ee8e2f5b6e5fc69729f2ad836a149a31c522cbec1cabcc1a9d9b1bfefb9742e8153e89f28f359e471",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "044ca8971cea10d6c809a6f3f7ca90b586f0f865e4fe46c210c004aafad5cb5cc4ad5a47f9453e1ad704378e6918885f",
+        additional_reseed: "",
+        entropy_pr: ["", ""],
+        additional: ["", ""],
+        returned: "f972df6e1cd295eb10c621c16e7987556961d6a1c0b6d5de9255eab7ede176051390796efd43289392c74ef0175c02691e81dbcdc3bb653aee3b6b131a5a90b3",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "c5099bc33e519da61129eb9e70a7ac56bc12d2bdcd9e5557537ec81ba2647facffa9669caafae691903184dc51b4d5a4",
+        nonce: "",
+        personalization: "e9caff0ee7f2b8c5b46a3197ee811e88ab2256d50acf19f3ea458818bbeebd834c0e60c154cfc10e652c08c6d6ea1889",
+        entropy_reseed: "f4558131cd5f54089718813e29515c55cd3bc1395e09a4d3d434d565158133cc67ed88ed25d51b32ab812fd0e626a848",
+        additional_reseed: "79462cfcc26ac410b07134b66cf41877c24468af8c5fff8fa6f33ce752d4906aa18ccd465a1df2ce9eaab9766a5866c8",
+        entropy_pr: ["", ""],
+        additional: ["edd5189cd86812ebda55cf8271e4aabf3ed6736b39589143041d66498a2382d4186c42f1ed87d7da563e1617dd3e9701", "f3c55cd9cfea2ba3df42a0073af7e8a2e46651faa3acfef812db5f708896df155ca0a2bf15e146d974c8d888f28942cb"],
+        returned: "02f66f5f0b09563f057a6bd826efff3d4f0d344418c09e4e8fb7c6262f65151d7dd600e8377975359be693a1f9ca8e9f64500e9e6db332bf04f8fa7b87b21062",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "085b287d5cf26cd43cd15d90a3144832de3bc1b39513c5f4f35656fc49bec8658266a922aa2632c09683ff0149225ca4",
+        nonce: "",
+        personalization: "",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["af9d1e1ca04443447a4e994fd6a9641ac4241db26272d3e97258997e78c2a92b32ca65c8e44343f954d5cc5b414eb2e9", "49f709b5c7d99b892b26a0742fae3fcbeffea9c9b5d063773f03d66e049a9647ae024f243515f70c76e7fb3eb62c22ad"],
+        additional: ["", ""],
+        returned: "829d8bd0ca5bb54598aa5287aec0e9dbadaeca3c671bf4e14667a5d7ea2df2c37356ac9a1097f756204295c60dc489222797409d84f97c72255a7724bf28fc02",
+    },
+    Vector {
+        key_len: 32,
+        derivation_function: false,
+        entropy: "eff9f2ccbc4b7996250e9ce4d82de90330f5058137ad205a8357ca5f6b3fe8f2c8656619f4d61308addf25213ba360d2",
+        nonce: "",
+        personalization: "658898dcce233df58c524e7b275cdff24b21ada534fb649064eddcba5176de82f8fdb7cf4babbfebda8ebb7041e8d57b",
+        entropy_reseed: "",
+        additional_reseed: "",
+        entropy_pr: ["056c02d4b39bf3a3e94adb652934eb530e997e35339f77c256d4e8fdf4a70d4b93bbd80de468cc71e662a95a6664d973", "53023cff2236c4fec7e10ed58f34c0082ebdb170d98bd9a5701decc6d3c87bab2685b9bb8517a29cd5bd983fb607171d"],
+        additional: ["ba7faf53b8005ea78c0008a735fb3c8f731e20ef9b74faf0461635794f49888f6838a0917844e4aaea2ff966a55bce47", "4254348b7fe0cefd74e703b1922dbc29c3442350915cff9e3305fc26a0300951e2acdda59247ae80317f93dc38a8f283"],
+        returned: "8e2c9d1dc7d6f5fc1f9f92105adb965d72aa378f2942d66dbe758b9ae482987b15fe99727ed71520b0a1d9bcf2279cb14ced2618b9a32ab52813d4d1d3ef8ffc",
+    },
+];
+
+fn returned_bits(v: &Vector) -> Vec<u8> {
+    let mut drbg = CtrDrbg::instantiate(v.key_len, v.derivation_function, &hex(v.entropy), &hex(v.nonce), &hex(v.personalization)).unwrap();
+
+    if !v.entropy_reseed.is_empty() {
+        drbg.reseed(&hex(v.entropy_reseed), &hex(v.additional_reseed)).unwrap();
+    }
+
+    let mut returned = [0u8; 64];
+    for (entropy, additional) in v.entropy_pr.iter().zip(v.additional) {
+        if entropy.is_empty() {
+            drbg.generate(&mut returned, &hex(additional)).unwrap();
+        } else {
+            drbg.generate_with_prediction_resistance(&hex(entropy), &mut returned, &hex(additional)).unwrap();
+        }
+    }
+
+    returned.to_vec()
+}
+
+#[test]
+fn cavp_vectors() {
+    for (i, v) in CAVP_VECTORS.iter().enumerate() {
+        assert_eq!(returned_bits(v), hex(v.returned), "vector {}", i);
+    }
+}
+
+#[test]
+fn cavp_style_vectors() {
+    for (i, v) in VECTORS.iter().enumerate() {
+        assert_eq!(returned_bits(v), hex(v.returned), "vector {}", i);
+    }
+}
+
+#[test]
+fn reseed_interval() {
+    let mut drbg = CtrDrbg::instantiate(16, true, &[1; 16], &[2; 8], b"").unwrap().with_reseed_interval(2);
+    let mut out = [0u8; 16];
+
+    drbg.generate(&mut out, b"").unwrap();
+    drbg.generate(&mut out, b"").unwrap();
+    assert!(matches!(drbg.generate(&mut out, b""), Err(AesError::ReseedRequired)));
+
+    drbg.reseed(&[3; 16], b"").unwrap();
+    drbg.generate(&mut out, b"").unwrap();
+
+    // fill reseeds by itself
+    drbg.fill(&mut out).unwrap();
+    drbg.fill(&mut out).unwrap();
+}
+
+#[test]
+fn rejects_bad_lengths() {
+    assert!(matches!(CtrDrbg::instantiate(20, true, &[0; 32], &[0; 16], b""), Err(AesError::InvalidKeyLength(20))));
+
+    // without the derivation function the entropy is exactly key length plus one block
+    assert!(matches!(CtrDrbg::instantiate(16, false, &[0; 16], b"", b""), Err(AesError::InvalidSeedLength(16))));
+    assert!(matches!(CtrDrbg::instantiate(16, false, &[0; 32], b"", &[0; 33]), Err(AesError::InvalidSeedLength(33))));
+
+    // with it at least the security strength, and half of that as nonce
+    assert!(matches!(CtrDrbg::instantiate(32, true, &[0; 31], &[0; 16], b""), Err(AesError::InvalidSeedLength(31))));
+    assert!(matches!(CtrDrbg::instantiate(32, true, &[0; 32], &[0; 15], b""), Err(AesError::InvalidSeedLength(15))));
+
+    let mut drbg = CtrDrbg::instantiate(16, true, &[0; 16], &[0; 8], b"").unwrap();
+    let mut big = vec![0u8; (1 << 16) + 1];
+    assert!(matches!(drbg.generate(&mut big, b""), Err(AesError::InvalidRequestLength(65537))));
+    drbg.fill(&mut big).unwrap();
+}
+
+#[test]
+fn ivs_for_encrypt() {
+    let mut drbg = CtrDrbg::from_os_random(32).unwrap().with_prediction_resistance();
+    let key = b"YELLOW SUBMARINE";
+
+    let first = drbg.next_iv().unwrap();
+    let second = drbg.next_iv().unwrap();
+    assert_ne!(first, second);
+
+    assert_ne!(encrypt(b"same message", key, first).unwrap(), encrypt(b"same message", key, second).unwrap());
+}
@@ -0,0 +1,81 @@
+// CBC ciphertext stealing tests. The CS3 column holds the RFC 3962
+// Appendix B vectors; CS1 and CS2 are the same ciphertexts reordered as the
+// SP 800-38A Addendum describes.
+
+mod common;
+
+use aes::{cts, Aes128, AesError, CtsVariant};
+use common::{hex, hex16};
+
+static KEY: &str = "636869636b656e207465726979616b69";
+
+static PLAINTEXT: &[u8] = b"I would like the General Gau's Chicken, please, and wonton soup.";
+
+// plaintext length, CS1, CS2, CS3
+static VECTORS: [(usize, &str, &str, &str); 7] = [
+    (16,
+     "97687268d6ecccc0c07b25e25ecfe584",
+     "97687268d6ecccc0c07b25e25ecfe584",
+     "97687268d6ecccc0c07b25e25ecfe584"),
+    (17,
+     "97c6353568f2bf8cb4d8a580362da7ff7f",
+     "c6353568f2bf8cb4d8a580362da7ff7f97",
+     "c6353568f2bf8cb4d8a580362da7ff7f97"),
+    (31,
+     "97687268d6ecccc0c07b25e25ecfe5fc00783e0efdb2c1d445d4c8eff7ed22",
+     "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5",
+     "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5"),
+    (32,
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a8",
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a8",
+     "39312523a78662d5be7fcbcc98ebf5a897687268d6ecccc0c07b25e25ecfe584"),
+    (47,
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5b3fffd940c16a18c1b5549d2f838029e",
+     "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5",
+     "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5"),
+    (48,
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a89dad8bbb96c4cdc03bc103e1a194bbd8",
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a89dad8bbb96c4cdc03bc103e1a194bbd8",
+     "97687268d6ecccc0c07b25e25ecfe5849dad8bbb96c4cdc03bc103e1a194bbd839312523a78662d5be7fcbcc98ebf5a8"),
+    (64,
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a89dad8bbb96c4cdc03bc103e1a194bbd84807efe836ee89a526730dbc2f7bc840",
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a89dad8bbb96c4cdc03bc103e1a194bbd84807efe836ee89a526730dbc2f7bc840",
+     "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a84807efe836ee89a526730dbc2f7bc8409dad8bbb96c4cdc03bc103e1a194bbd8"),
+];
+
+#[test]
+fn all_variants() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    for (len, cs1, cs2, cs3) in VECTORS {
+        let plaintext = &PLAINTEXT[..len];
+
+        for (variant, expected) in [(CtsVariant::Cs1, cs1), (CtsVariant::Cs2, cs2), (CtsVariant::Cs3, cs3)] {
+            let ciphertext = cts::encrypt(&aes, [0; 16], plaintext, variant).unwrap();
+            assert_eq!(ciphertext, hex(expected), "{:?} length {}", variant, len);
+            assert_eq!(cts::decrypt(&aes, [0; 16], &ciphertext, variant).unwrap(), plaintext);
+        }
+    }
+}
+
+#[test]
+fn length_preserving_with_iv() {
+    let aes = Aes128::new(&hex16(KEY));
+    let iv = [0x42; 16];
+
+    for len in 16..=PLAINTEXT.len() {
+        let plaintext = &PLAINTEXT[..len];
+        let ciphertext = cts::encrypt(&aes, iv, plaintext, CtsVariant::Cs2).unwrap();
+
+        assert_eq!(ciphertext.len(), len);
+        assert_eq!(cts::decrypt(&aes, iv, &ciphertext, CtsVariant::Cs2).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn needs_a_full_block() {
+    let aes = Aes128::new(&hex16(KEY));
+
+    assert!(matches!(cts::encrypt(&aes, [0; 16], &PLAINTEXT[..15], CtsVariant::Cs3), Err(AesError::InvalidPlaintextLength(15))));
+    assert!(matches!(cts::decrypt(&aes, [0; 16], &[], CtsVariant::Cs1), Err(AesError::InvalidCiphertextLength(0))));
+}
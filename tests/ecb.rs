@@ -0,0 +1,42 @@
+// ECB known-answer tests from SP 800-38A Appendix F.1
+
+mod common;
+
+use aes::{ecb, Aes, Aes128, AesError};
+use common::hex;
+
+static PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51
+                          30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710";
+
+static VECTORS: [(&str, &str); 3] = [
+    // F.1.1/F.1.2 ECB-AES128
+    ("2b7e151628aed2a6abf7158809cf4f3c",
+     "3ad77bb40d7a3660a89ecaf32466ef97 f5d3d58503b9699de785895a96fdbaaf
+      43b1cd7f598ece23881b00e3ed030688 7b0c785e27e8ad3f8223207104725dd4"),
+    // F.1.3/F.1.4 ECB-AES192
+    ("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+     "bd334f1d6e45f25ff712a214571fa5cc 974104846d0ad3ad7734ecb3ecee4eef
+      ef7afd2270e2e60adce0ba2face6444e 9a4b41ba738d6c72fb16691603c18e0e"),
+    // F.1.5/F.1.6 ECB-AES256
+    ("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+     "f3eed1bdb5d2a03c064b5a7e3db181f8 591ccb10d410ed26dc5ba74a31362870
+      b6ed21b99ca6f4f9f153e7b1beafed1d 23304b7a39f9f3ff067d8d8f9e24ecc7"),
+];
+
+#[test]
+fn sp800_38a_vectors() {
+    for (key, ciphertext) in VECTORS {
+        let aes = Aes::new(&hex(key)).unwrap();
+
+        assert_eq!(ecb::encrypt_blocks(&aes, &hex(PLAINTEXT)).unwrap(), hex(ciphertext));
+        assert_eq!(ecb::decrypt_blocks(&aes, &hex(ciphertext)).unwrap(), hex(PLAINTEXT));
+    }
+}
+
+#[test]
+fn rejects_partial_blocks() {
+    let aes = Aes128::new(&[0; 16]);
+
+    assert!(matches!(ecb::encrypt_blocks(&aes, &[0; 17]), Err(AesError::InvalidPlaintextLength(17))));
+    assert!(matches!(ecb::decrypt_blocks(&aes, &[0; 15]), Err(AesError::InvalidCiphertextLength(15))));
+}
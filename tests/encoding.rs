@@ -0,0 +1,57 @@
+// Tests for the hex and base64 key decoders
+
+use aes::encoding::{decode_base64, decode_hex};
+use aes::{determine_key_length, AesError};
+
+#[test]
+fn hex() {
+    assert_eq!(decode_hex("").unwrap(), b"");
+    assert_eq!(decode_hex("00ff7Fa0").unwrap(), [0x00, 0xff, 0x7f, 0xa0]);
+
+    for bad in ["0", "0g", "+f", "00 ff", "-1", "éé"] {
+        assert!(matches!(decode_hex(bad), Err(AesError::InvalidKeyEncoding)), "{:?}", bad);
+    }
+}
+
+#[test]
+fn base64() {
+    // RFC 4648 section 10
+    for (decoded, encoded) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="),
+                               ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")] {
+        assert_eq!(decode_base64(encoded).unwrap(), decoded.as_bytes(), "{:?}", encoded);
+        assert_eq!(decode_base64(encoded.trim_end_matches('=')).unwrap(), decoded.as_bytes());
+    }
+
+    assert_eq!(decode_base64("+/+/").unwrap(), [0xfb, 0xff, 0xbf]);
+}
+
+#[test]
+fn base64_must_be_canonical() {
+    for bad in [
+        // non-zero bits after the last byte
+        "Zh==", "Zm9=", "AAECAwQFBgcICQoLDA0ODx==",
+        // padding that does not match the length
+        "Zg=", "Zg===", "Zm8==", "Zm9v=", "=",
+        // a single character in the last group
+        "Z", "Zm9vY",
+        // characters outside the alphabet
+        "Zm9v YmFy", "Zm9v-_Fy", "Zg==Zg==",
+    ] {
+        assert!(matches!(decode_base64(bad), Err(AesError::InvalidKeyEncoding)), "{:?}", bad);
+    }
+}
+
+#[test]
+fn every_key_size() {
+    let cases = [
+        ("000102030405060708090a0b0c0d0e0f", "AAECAwQFBgcICQoLDA0ODw=="),
+        ("000102030405060708090a0b0c0d0e0f1011121314151617", "AAECAwQFBgcICQoLDA0ODxAREhMUFRYX"),
+        ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="),
+    ];
+
+    for (hex, base64) in cases {
+        let key = decode_hex(hex).unwrap();
+        assert_eq!(decode_base64(base64).unwrap(), key);
+        assert!(determine_key_length(&key).is_ok());
+    }
+}
@@ -0,0 +1,128 @@
+// AES-GCM-SIV tests with the RFC 8452 Appendix C vectors
+
+mod common;
+
+use aes::{AesError, GcmSiv};
+use common::hex;
+
+static NONCE: &str = "030000000000000000000000";
+
+// aad, plaintext, ciphertext || tag
+type Vector = (&'static str, &'static str, &'static str);
+
+// C.1, key 01000000000000000000000000000000
+static AES128: [Vector; 13] = [
+    ("", "",
+     "dc20e2d83f25705bb49e439eca56de25"),
+    ("", "0100000000000000",
+     "b5d839330ac7b786578782fff6013b815b287c22493a364c"),
+    ("", "010000000000000000000000",
+     "7323ea61d05932260047d942a4978db357391a0bc4fdec8b0d106639"),
+    ("", "01000000000000000000000000000000",
+     "743f7c8077ab25f8624e2e948579cf77303aaf90f6fe21199c6068577437a0c4"),
+    ("", "0100000000000000000000000000000002000000000000000000000000000000",
+     "84e07e62ba83a6585417245d7ec413a9fe427d6315c09b57ce45f2e3936a94451a8e45dcd4578c667cd86847bf6155ff"),
+    ("", "010000000000000000000000000000000200000000000000000000000000000003000000000000000000000000000000",
+     "3fd24ce1f5a67b75bf2351f181a475c7b800a5b4d3dcf70106b1eea82fa1d64df42bf7226122fa92e17a40eeaac1201b5e6e311dbf395d35b0fe39c2714388f8"),
+    ("", "01000000000000000000000000000000020000000000000000000000000000000300000000000000000000000000000004000000000000000000000000000000",
+     "2433668f1058190f6d43e360f4f35cd8e475127cfca7028ea8ab5c20f7ab2af02516a2bdcbc08d521be37ff28c152bba36697f25b4cd169c6590d1dd39566d3f8a263dd317aa88d56bdf3936dba75bb8"),
+    ("01", "0200000000000000",
+     "1e6daba35669f4273b0a1a2560969cdf790d99759abd1508"),
+    ("01", "020000000000000000000000",
+     "296c7889fd99f41917f4462008299c5102745aaa3a0c469fad9e075a"),
+    ("01", "02000000000000000000000000000000",
+     "e2b0c5da79a901c1745f700525cb335b8f8936ec039e4e4bb97ebd8c4457441f"),
+    ("01", "0200000000000000000000000000000003000000000000000000000000000000",
+     "620048ef3c1e73e57e02bb8562c416a319e73e4caac8e96a1ecb2933145a1d71e6af6a7f87287da059a71684ed3498e1"),
+    ("01", "020000000000000000000000000000000300000000000000000000000000000004000000000000000000000000000000",
+     "50c8303ea93925d64090d07bd109dfd9515a5a33431019c17d93465999a8b0053201d723120a8562b838cdff25bf9d1e6a8cc3865f76897c2e4b245cf31c51f2"),
+    ("01", "02000000000000000000000000000000030000000000000000000000000000000400000000000000000000000000000005000000000000000000000000000000",
+     "2f5c64059db55ee0fb847ed513003746aca4e61c711b5de2e7a77ffd02da42feec601910d3467bb8b36ebbaebce5fba30d36c95f48a3e7980f0e7ac299332a80cdc46ae475563de037001ef84ae21744"),
+];
+
+// C.2, key 0100000000000000000000000000000000000000000000000000000000000000
+static AES256: [Vector; 13] = [
+    ("", "",
+     "07f5f4169bbf55a8400cd47ea6fd400f"),
+    ("", "0100000000000000",
+     "c2ef328e5c71c83b843122130f7364b761e0b97427e3df28"),
+    ("", "010000000000000000000000",
+     "9aab2aeb3faa0a34aea8e2b18ca50da9ae6559e48fd10f6e5c9ca17e"),
+    ("", "01000000000000000000000000000000",
+     "85a01b63025ba19b7fd3ddfc033b3e76c9eac6fa700942702e90862383c6c366"),
+    ("", "0100000000000000000000000000000002000000000000000000000000000000",
+     "4a6a9db4c8c6549201b9edb53006cba821ec9cf850948a7c86c68ac7539d027fe819e63abcd020b006a976397632eb5d"),
+    ("", "010000000000000000000000000000000200000000000000000000000000000003000000000000000000000000000000",
+     "c00d121893a9fa603f48ccc1ca3c57ce7499245ea0046db16c53c7c66fe717e39cf6c748837b61f6ee3adcee17534ed5790bc96880a99ba804bd12c0e6a22cc4"),
+    ("", "01000000000000000000000000000000020000000000000000000000000000000300000000000000000000000000000004000000000000000000000000000000",
+     "c2d5160a1f8683834910acdafc41fbb1632d4a353e8b905ec9a5499ac34f96c7e1049eb080883891a4db8caaa1f99dd004d80487540735234e3744512c6f90ce112864c269fc0d9d88c61fa47e39aa08"),
+    ("01", "0200000000000000",
+     "1de22967237a813291213f267e3b452f02d01ae33e4ec854"),
+    ("01", "020000000000000000000000",
+     "163d6f9cc1b346cd453a2e4cc1a4a19ae800941ccdc57cc8413c277f"),
+    ("01", "02000000000000000000000000000000",
+     "c91545823cc24f17dbb0e9e807d5ec17b292d28ff61189e8e49f3875ef91aff7"),
+    ("01", "0200000000000000000000000000000003000000000000000000000000000000",
+     "07dad364bfc2b9da89116d7bef6daaaf6f255510aa654f920ac81b94e8bad365aea1bad12702e1965604374aab96dbbc"),
+    ("01", "020000000000000000000000000000000300000000000000000000000000000004000000000000000000000000000000",
+     "c67a1f0f567a5198aa1fcc8e3f21314336f7f51ca8b1af61feac35a86416fa47fbca3b5f749cdf564527f2314f42fe2503332742b228c647173616cfd44c54eb"),
+    ("01", "02000000000000000000000000000000030000000000000000000000000000000400000000000000000000000000000005000000000000000000000000000000",
+     "67fd45e126bfb9a79930c43aad2d36967d3f0e4d217c1e551f59727870beefc98cb933a8fce9de887b1e40799988db1fc3f91880ed405b2dd298318858467c895bde0285037c5de81e5b570a049b62a0"),
+];
+
+fn check(key: &[u8], vectors: &[Vector]) {
+    let siv = GcmSiv::new(key).unwrap();
+
+    for (aad, plaintext, sealed) in vectors {
+        assert_eq!(siv.encrypt(&hex(NONCE), &hex(aad), &hex(plaintext)).unwrap(), hex(sealed));
+        assert_eq!(siv.decrypt(&hex(NONCE), &hex(aad), &hex(sealed)).unwrap(), hex(plaintext));
+    }
+}
+
+#[test]
+fn aes128_vectors() {
+    let mut key = [0u8; 16];
+    key[0] = 1;
+    check(&key, &AES128);
+}
+
+#[test]
+fn aes256_vectors() {
+    let mut key = [0u8; 32];
+    key[0] = 1;
+    check(&key, &AES256);
+}
+
+#[test]
+fn counter_wrap() {
+    // C.3, the 32-bit counter wraps around
+    let siv = GcmSiv::new(&[0; 32]).unwrap();
+
+    let cases = [
+        ("000000000000000000000000000000004db923dc793ee6497c76dcc03a98e108",
+         "f3f80f2cf0cb2dd9c5984fcda908456cc537703b5ba70324a6793a7bf218d3eaffffffff000000000000000000000000"),
+        ("eb3640277c7ffd1303c7a542d02d3e4c0000000000000000",
+         "18ce4f0b8cb4d0cac65fea8f79257b20888e53e72299e56dffffffff000000000000000000000000"),
+    ];
+
+    for (plaintext, sealed) in cases {
+        assert_eq!(siv.encrypt(&[0; 12], &[], &hex(plaintext)).unwrap(), hex(sealed));
+        assert_eq!(siv.decrypt(&[0; 12], &[], &hex(sealed)).unwrap(), hex(plaintext));
+    }
+}
+
+#[test]
+fn rejects_forgeries() {
+    let siv = GcmSiv::new(&[9; 16]).unwrap();
+    let sealed = siv.encrypt(&[1; 12], b"aad", b"a secret message").unwrap();
+
+    for i in [0, sealed.len() - 1] {
+        let mut flipped = sealed.clone();
+        flipped[i] ^= 1;
+        assert!(matches!(siv.decrypt(&[1; 12], b"aad", &flipped), Err(AesError::AuthenticationFailed)));
+    }
+
+    assert!(matches!(siv.decrypt(&[2; 12], b"aad", &sealed), Err(AesError::AuthenticationFailed)));
+    assert!(matches!(siv.encrypt(&[1; 16], b"", b""), Err(AesError::InvalidNonceLength(16))));
+    assert!(matches!(GcmSiv::new(&[0; 24]), Err(AesError::InvalidKeyLength(24))));
+}
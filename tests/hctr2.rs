@@ -0,0 +1,100 @@
+// HCTR2 tests. The paper's reference vectors are not bundled here, these
+// were produced by an independent Python model of the construction (AES from
+// pyca/cryptography, POLYVAL as plain field arithmetic checked against the
+// RFC 8452 example).
+
+mod common;
+
+use aes::{Aes, AesError, Hctr2};
+use common::hex;
+
+// the message is byte i = 7i + 3
+fn message(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+fn hctr2(key_len: usize) -> Hctr2<Aes> {
+    let key: Vec<u8> = (0..key_len as u8).collect();
+    Hctr2::new(Aes::new(&key).unwrap())
+}
+
+// key length (key is 00 01 02 ..), tweak, message length, ciphertext
+static VECTORS: [(usize, &str, usize, &str); 14] = [
+    (16, "", 16,
+     "e860e1551fc890f2c9787dd773007525"),
+    (16, "", 17,
+     "620b5b8b15a578a9d180dc03b5a43d2e48"),
+    (16, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 16,
+     "0a4b3f6b88bc074d202c7a18b2a75394"),
+    (16, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 31,
+     "aba2a3f9b7b37145642135b903b6f0574a9b50a7291a6f9f532267d0060768"),
+    (16, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 32,
+     "b3f2af85b0f6756b9542b4938bcd095451da010889c862b76392087d7f50856e"),
+    (16, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 48,
+     "ff77fb77e8bc5f3dcfa7e956b3f76ea62d81268e111b2aa6477e2d3f7843b8b8af9466a840fd6783fd8ba39c351bcc61"),
+    (16, "00010203040506", 100,
+     "058cebd0c301ee2be168929ac2c41af47732536d48fd5eb19f434bf3d0f7bdc62a8434d2264ff75c1d7c61cefd0c78ebe01f626c5bf3ee095479ca43d18d04f4ab39c8397ed6f650bed0f591477063c3a00cf5725a2fa3b261d7eb3e62c1cc6e331b2852"),
+    (32, "", 16,
+     "db04ae0b0c2665b355ec53bbf58a7f30"),
+    (32, "", 17,
+     "aa0af023fd24772b61b22ef03b132c0f6f"),
+    (32, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 16,
+     "d88ba66da85cf9b973fe31082d759cf5"),
+    (32, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 31,
+     "75024b173c44d57d1ebc1f2160ca4e581663bb7f6f69d253f6f33a2724ba3f"),
+    (32, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 32,
+     "33df28c1294055bc5db61bb0dbdeb7c87477e927d1f3e5d143d229adc81508c8"),
+    (32, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", 48,
+     "215ff87e2fd9776309f9e3eeddec29d1682260841420e9cff6d3d11f92becc94cd4874a0322914f3e402a3664a1780ab"),
+    (32, "00010203040506", 100,
+     "9024d010435ef1fc88248d74ced297676754fe0c5b3cd38084d36d70c155742c50e906d1a67b55149a09617105cb9572bc424abda3ab7ca97ecacacb74636f87566f6b974c9afcbba07216a4de8cc40c1d08c9a16ef49dd39d2e6f56b0a630c7f22efdb5"),
+];
+
+#[test]
+fn model_vectors() {
+    for (key_len, tweak, len, expected) in VECTORS {
+        let hctr2 = hctr2(key_len);
+        let tweak = hex(tweak);
+
+        let mut buf = message(len);
+        hctr2.encrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(buf, hex(expected), "key {} tweak {} length {}", key_len, tweak.len(), len);
+
+        hctr2.decrypt(&tweak, &mut buf).unwrap();
+        assert_eq!(buf, message(len));
+    }
+}
+
+#[test]
+fn changes_spread_over_the_whole_message() {
+    let hctr2 = hctr2(32);
+    let tweak = b"file name tweak";
+
+    let mut reference = message(64);
+    hctr2.encrypt(tweak, &mut reference).unwrap();
+
+    // flipping the last plaintext bit or the tweak changes the first block too
+    let mut flipped = message(64);
+    flipped[63] ^= 1;
+    hctr2.encrypt(tweak, &mut flipped).unwrap();
+    assert_ne!(flipped[..16], reference[..16]);
+    assert_ne!(flipped[48..], reference[48..]);
+
+    let mut other_tweak = message(64);
+    hctr2.encrypt(b"file name tweaK", &mut other_tweak).unwrap();
+    assert_ne!(other_tweak[..16], reference[..16]);
+    assert_ne!(other_tweak[48..], reference[48..]);
+
+    // decrypting under the wrong tweak scrambles everything
+    hctr2.decrypt(b"file name tweaK", &mut reference).unwrap();
+    assert_ne!(reference[..16], message(64)[..16]);
+    assert_ne!(reference[48..], message(64)[48..]);
+}
+
+#[test]
+fn needs_a_full_block() {
+    let hctr2 = hctr2(16);
+
+    assert!(matches!(hctr2.encrypt(b"", &mut [0; 15]), Err(AesError::InvalidPlaintextLength(15))));
+    assert!(matches!(hctr2.decrypt(b"", &mut []), Err(AesError::InvalidCiphertextLength(0))));
+}
@@ -0,0 +1,77 @@
+// Key wrap tests with the RFC 3394 section 4 and RFC 5649 section 6 vectors
+
+mod common;
+
+use aes::{kw, Aes, AesError};
+use common::hex;
+
+static KEK: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+static KEY_DATA: &str = "00112233445566778899aabbccddeeff000102030405060708090a0b0c0d0e0f";
+
+// kek bytes, key data bytes, wrapped
+static RFC3394: [(usize, usize, &str); 6] = [
+    (16, 16, "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5"),
+    (24, 16, "96778b25ae6ca435f92b5b97c050aed2468ab8a17ad84e5d"),
+    (32, 16, "64e8c3f9ce0f5ba263e9777905818a2a93c8191e7d6e8ae7"),
+    (24, 24, "031d33264e15d33268f24ec260743edce1c6c7ddee725a936ba814915c6762d2"),
+    (32, 24, "a8f9bc1612c68b3ff6e6f4fbe30e71e4769c8b80a32cb8958cd5d17d6b254da1"),
+    (32, 32, "28c9f404c4b810f4cbccb35cfb87f8263f5786e2d80ed326cbc7f0e71a99f43bfb988b9b7a02dd21"),
+];
+
+static RFC5649_KEK: &str = "5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8";
+
+static RFC5649: [(&str, &str); 2] = [
+    ("c37b7e6492584340bed12207808941155068f738", "138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a"),
+    ("466f7250617369", "afbeb0f07dfbf5419200f2ccb50bb24f"),
+];
+
+#[test]
+fn rfc3394_vectors() {
+    for (kek_len, key_len, wrapped) in RFC3394 {
+        let kek = Aes::new(&hex(KEK)[..kek_len]).unwrap();
+        let key = &hex(KEY_DATA)[..key_len];
+
+        assert_eq!(kw::wrap(&kek, key).unwrap(), hex(wrapped));
+        assert_eq!(kw::unwrap(&kek, &hex(wrapped)).unwrap(), key);
+    }
+}
+
+#[test]
+fn rfc5649_vectors() {
+    let kek = Aes::new(&hex(RFC5649_KEK)).unwrap();
+
+    for (key, wrapped) in RFC5649 {
+        assert_eq!(kw::wrap_pad(&kek, &hex(key)).unwrap(), hex(wrapped));
+        assert_eq!(kw::unwrap_pad(&kek, &hex(wrapped)).unwrap(), hex(key));
+    }
+}
+
+#[test]
+fn wrong_kek() {
+    let kek = Aes::new(&hex(KEK)[..16]).unwrap();
+    let other = Aes::new(&[0x42; 16]).unwrap();
+
+    let wrapped = kw::wrap(&kek, &hex(KEY_DATA)).unwrap();
+    assert!(matches!(kw::unwrap(&other, &wrapped), Err(AesError::AuthenticationFailed)));
+
+    for len in [1, 7, 8, 9, 20] {
+        let wrapped = kw::wrap_pad(&kek, &hex(KEY_DATA)[..len]).unwrap();
+        assert!(matches!(kw::unwrap_pad(&other, &wrapped), Err(AesError::AuthenticationFailed)));
+
+        // RFC 3394 and 5649 outputs are not interchangeable
+        if wrapped.len() >= 24 {
+            assert!(matches!(kw::unwrap(&kek, &wrapped), Err(AesError::AuthenticationFailed)));
+        }
+    }
+}
+
+#[test]
+fn length_checks() {
+    let kek = Aes::new(&hex(KEK)[..16]).unwrap();
+
+    assert!(matches!(kw::wrap(&kek, &[0; 8]), Err(AesError::InvalidPlaintextLength(8))));
+    assert!(matches!(kw::wrap(&kek, &[0; 20]), Err(AesError::InvalidPlaintextLength(20))));
+    assert!(matches!(kw::wrap_pad(&kek, &[]), Err(AesError::InvalidPlaintextLength(0))));
+    assert!(matches!(kw::unwrap(&kek, &[0; 16]), Err(AesError::InvalidCiphertextLength(16))));
+    assert!(matches!(kw::unwrap_pad(&kek, &[0; 12]), Err(AesError::InvalidCiphertextLength(12))));
+}
@@ -0,0 +1,144 @@
+// OCB3 tests with the RFC 7253 Appendix A sample results
+
+mod common;
+
+use aes::{Aes, Aes128, AesError, Ocb};
+use common::hex;
+
+static KEY: &str = "000102030405060708090a0b0c0d0e0f";
+
+// nonce, associated data, plaintext, ciphertext || tag
+static SAMPLES: [(&str, &str, &str, &str); 16] = [
+    ("bbaa99887766554433221100", "",
+     "",
+     "785407bfffc8ad9edcc5520ac9111ee6"),
+    ("bbaa99887766554433221101", "0001020304050607",
+     "0001020304050607",
+     "6820b3657b6f615a5725bda0d3b4eb3a257c9af1f8f03009"),
+    ("bbaa99887766554433221102", "0001020304050607",
+     "",
+     "81017f8203f081277152fade694a0a00"),
+    ("bbaa99887766554433221103", "",
+     "0001020304050607",
+     "45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa9"),
+    ("bbaa99887766554433221104", "000102030405060708090a0b0c0d0e0f",
+     "000102030405060708090a0b0c0d0e0f",
+     "571d535b60b277188be5147170a9a22c3ad7a4ff3835b8c5701c1ccec8fc3358"),
+    ("bbaa99887766554433221105", "000102030405060708090a0b0c0d0e0f",
+     "",
+     "8cf761b6902ef764462ad86498ca6b97"),
+    ("bbaa99887766554433221106", "",
+     "000102030405060708090a0b0c0d0e0f",
+     "5ce88ec2e0692706a915c00aeb8b2396f40e1c743f52436bdf06d8fa1eca343d"),
+    ("bbaa99887766554433221107", "000102030405060708090a0b0c0d0e0f1011121314151617",
+     "000102030405060708090a0b0c0d0e0f1011121314151617",
+     "1ca2207308c87c010756104d8840ce1952f09673a448a122c92c62241051f57356d7f3c90bb0e07f"),
+    ("bbaa99887766554433221108", "000102030405060708090a0b0c0d0e0f1011121314151617",
+     "",
+     "6dc225a071fc1b9f7c69f93b0f1e10de"),
+    ("bbaa99887766554433221109", "",
+     "000102030405060708090a0b0c0d0e0f1011121314151617",
+     "221bd0de7fa6fe993eccd769460a0af2d6cded0c395b1c3ce725f32494b9f914d85c0b1eb38357ff"),
+    ("bbaa9988776655443322110a", "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     "bd6f6c496201c69296c11efd138a467abd3c707924b964deaffc40319af5a48540fbba186c5553c68ad9f592a79a4240"),
+    ("bbaa9988776655443322110b", "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     "",
+     "fe80690bee8a485d11f32965bc9d2a32"),
+    ("bbaa9988776655443322110c", "",
+     "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+     "2942bfc773bda23cabc6acfd9bfd5835bd300f0973792ef46040c53f1432bcdfb5e1dde3bc18a5f840b52e653444d5df"),
+    ("bbaa9988776655443322110d", "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627",
+     "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627",
+     "d5ca91748410c1751ff8a2f618255b68a0a12e093ff454606e59f9c1d0ddc54b65e8628e568bad7aed07ba06a4a69483a7035490c5769e60"),
+    ("bbaa9988776655443322110e", "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627",
+     "",
+     "c5cd9d1850c141e358649994ee701b68"),
+    ("bbaa9988776655443322110f", "",
+     "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627",
+     "4412923493c57d5de0d700f753cce0d1d2d95060122e9f15a5ddbfc5787e50b5cc55ee507bcb084e479ad363ac366b95a98ca5f3000b1479"),
+];
+
+#[test]
+fn sample_results() {
+    let ocb = Ocb::new(Aes128::new(&hex(KEY).try_into().unwrap()), 16).unwrap();
+
+    for (nonce, aad, plaintext, sealed) in SAMPLES {
+        assert_eq!(ocb.encrypt(&hex(nonce), &hex(aad), &hex(plaintext)).unwrap(), hex(sealed));
+        assert_eq!(ocb.decrypt(&hex(nonce), &hex(aad), &hex(sealed)).unwrap(), hex(plaintext));
+    }
+}
+
+#[test]
+fn sample_with_96_bit_tag() {
+    let ocb = Ocb::new(Aes::new(&hex("0f0e0d0c0b0a09080706050403020100")).unwrap(), 12).unwrap();
+    let data: Vec<u8> = (0..40).collect();
+    let sealed = hex("1792a4e31e0755fb03e31b22116e6c2ddf9efd6e33d536f1a0124b0a55bae884
+                      ed93481529c76b6ad0c515f4d1cdd4fdac4f02aa");
+
+    assert_eq!(ocb.encrypt(&hex("bbaa9988776655443322110d"), &data, &data).unwrap(), sealed);
+    assert_eq!(ocb.decrypt(&hex("bbaa9988776655443322110d"), &data, &sealed).unwrap(), data);
+}
+
+/**
+    The iterated test of RFC 7253 Appendix A over every key size and tag length
+**/
+#[test]
+fn iterated_results() {
+    let results = [
+        (16, 16, "67e944d23256c5e0b6c61fa22fdf1ea2"),
+        (24, 16, "f673f2c3e7174aae7bae986ca9f29e17"),
+        (32, 16, "d90eb8e9c977c88b79dd793d7ffa161c"),
+        (16, 12, "77a3d8e73589158d25d01209"),
+        (24, 12, "05d56ead2752c86be6932c5e"),
+        (32, 12, "5458359ac23b0cba9e6330dd"),
+        (16, 8, "192c9b7bd90ba06a"),
+        (24, 8, "0066bc6e0ef34e24"),
+        (32, 8, "7d4ea5d445501cbe"),
+    ];
+
+    for (key_len, tag_len, expected) in results {
+        let mut key = vec![0u8; key_len];
+        key[key_len - 1] = (tag_len * 8) as u8;
+        let ocb = Ocb::new(Aes::new(&key).unwrap(), tag_len).unwrap();
+
+        let nonce = |n: usize| (n as u128).to_be_bytes()[4..].to_vec();
+
+        let mut c: Vec<u8> = Vec::new();
+        for i in 0..128 {
+            let s = vec![0u8; i];
+            c.extend(ocb.encrypt(&nonce(3 * i + 1), &s, &s).unwrap());
+            c.extend(ocb.encrypt(&nonce(3 * i + 2), &[], &s).unwrap());
+            c.extend(ocb.encrypt(&nonce(3 * i + 3), &s, &[]).unwrap());
+        }
+
+        assert_eq!(ocb.encrypt(&nonce(385), &c, &[]).unwrap(), hex(expected));
+    }
+}
+
+#[test]
+fn rejects_forgeries() {
+    let ocb = Ocb::new(Aes128::new(&hex(KEY).try_into().unwrap()), 16).unwrap();
+    let (nonce, aad, _, sealed) = SAMPLES[13];
+
+    for i in [0, 20, hex(sealed).len() - 1] {
+        let mut flipped = hex(sealed);
+        flipped[i] ^= 1;
+        assert!(matches!(ocb.decrypt(&hex(nonce), &hex(aad), &flipped), Err(AesError::AuthenticationFailed)));
+    }
+}
+
+#[test]
+fn parameters() {
+    let aes = Aes128::new(&[0; 16]);
+
+    assert!(matches!(Ocb::new(&aes, 10), Err(AesError::InvalidTagLength(10))));
+
+    let ocb = Ocb::new(&aes, 16).unwrap();
+    assert!(matches!(ocb.encrypt(&[], &[], b"x"), Err(AesError::InvalidNonceLength(0))));
+    assert!(matches!(ocb.encrypt(&[0; 16], &[], b"x"), Err(AesError::InvalidNonceLength(16))));
+
+    // short nonces are allowed
+    let sealed = ocb.encrypt(&[7], b"", b"short nonce").unwrap();
+    assert_eq!(ocb.decrypt(&[7], b"", &sealed).unwrap(), b"short nonce");
+}
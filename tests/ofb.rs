@@ -0,0 +1,134 @@
+// OFB known-answer tests from SP 800-38A Appendix F.4
+
+mod common;
+
+use std::io::{self, Write};
+
+use aes::{Aes, AesError, Ofb, OfbState};
+use common::{hex, hex16};
+
+static IV: &str = "000102030405060708090a0b0c0d0e0f";
+
+static PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a ae2d8a571e03ac9c9eb76fac45af8e51
+                          30c81c46a35ce411e5fbc1191a0a52ef f69f2445df4f9b17ad2b417be66c3710";
+
+static VECTORS: [(&str, &str); 3] = [
+    // F.4.1/F.4.2 OFB-AES128
+    ("2b7e151628aed2a6abf7158809cf4f3c",
+     "3b3fd92eb72dad20333449f8e83cfb4a 7789508d16918f03f53c52dac54ed825
+      9740051e9c5fecf64344f7a82260edcc 304c6528f659c77866a510d9c1d6ae5e"),
+    // F.4.3/F.4.4 OFB-AES192
+    ("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+     "cdc80d6fddf18cab34c25909c99a4174 fcc28b8d4c63837c09e81700c1100401
+      8d9a9aeac0f6596f559c6d4daf59a5f2 6d9f200857ca6c3e9cac524bd9acc92a"),
+    // F.4.5/F.4.6 OFB-AES256
+    ("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+     "dc7e84bfda79164b7ecd8486985d3860 4febdc6740d20b3ac88f6ad82a4fb08d
+      71ab47a086e86eedf39d1c5bba97c408 0126141d67f37be8538f5a8be740e484"),
+];
+
+#[test]
+fn sp800_38a_vectors() {
+    for (key, ciphertext) in VECTORS {
+        let aes = Aes::new(&hex(key)).unwrap();
+
+        let mut data = hex(PLAINTEXT);
+        Ofb::new(&aes, hex16(IV)).apply_keystream(&mut data);
+        assert_eq!(data, hex(ciphertext));
+
+        Ofb::new(&aes, hex16(IV)).apply_keystream(&mut data);
+        assert_eq!(data, hex(PLAINTEXT));
+    }
+}
+
+#[test]
+fn pause_and_resume() {
+    for (key, ciphertext) in VECTORS {
+        let aes = Aes::new(&hex(key)).unwrap();
+        let mut data = hex(PLAINTEXT);
+
+        // stop mid-block, save the state and continue with a fresh object
+        let state = {
+            let mut ofb = Ofb::new(&aes, hex16(IV));
+            ofb.apply_keystream(&mut data[..13]);
+            ofb.state()
+        };
+
+        let mut ofb = Ofb::resume(&aes, state).unwrap();
+        ofb.apply_keystream(&mut data[13..40]);
+        ofb.apply_keystream(&mut data[40..]);
+        assert_eq!(data, hex(ciphertext));
+    }
+}
+
+#[test]
+fn writer() {
+    let (key, ciphertext) = VECTORS[0];
+    let aes = Aes::new(&hex(key)).unwrap();
+    let plaintext = hex(PLAINTEXT);
+
+    let mut writer = Ofb::new(&aes, hex16(IV)).writer(Vec::new());
+    for chunk in plaintext.chunks(5) {
+        writer.write_all(chunk).unwrap();
+    }
+    assert_eq!(writer.state().offset, 16);
+
+    let (_, out) = writer.into_parts();
+    assert_eq!(out, hex(ciphertext));
+}
+
+#[test]
+fn resume_rejects_bad_offset() {
+    let aes = Aes::new(&hex(VECTORS[0].0)).unwrap();
+
+    let state = OfbState { register: hex16(IV), offset: 17 };
+    assert!(matches!(Ofb::resume(&aes, state), Err(AesError::InvalidStateOffset(17))));
+
+    // a fully used register is valid and continues with E(register)
+    let state = OfbState { register: hex16(IV), offset: 16 };
+    let mut data = hex(PLAINTEXT);
+    Ofb::resume(&aes, state).unwrap().apply_keystream(&mut data);
+    assert_eq!(data, hex(VECTORS[0].1));
+}
+
+// accepts a fixed number of writes, then fails
+struct FailingWriter {
+    written: Vec<u8>,
+    writes_left: usize,
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.writes_left == 0 {
+            return Err(io::Error::other("disk full"));
+        }
+
+        self.writes_left -= 1;
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_error_keeps_state() {
+    let (key, ciphertext) = VECTORS[0];
+    let aes = Aes::new(&hex(key)).unwrap();
+    let plaintext = hex(PLAINTEXT);
+
+    let mut writer = Ofb::new(&aes, hex16(IV)).writer(FailingWriter { written: Vec::new(), writes_left: 1 });
+    writer.write_all(&plaintext[..20]).unwrap();
+    let state = writer.state();
+
+    // the failed write leaves the keystream where the output stopped
+    assert!(writer.write_all(&plaintext[20..]).is_err());
+    assert_eq!(writer.state(), state);
+
+    let (_, inner) = writer.into_parts();
+    let mut rest = plaintext[20..].to_vec();
+    Ofb::resume(&aes, state).unwrap().apply_keystream(&mut rest);
+    assert_eq!([inner.written, rest].concat(), hex(ciphertext));
+}
@@ -0,0 +1,140 @@
+// Padding scheme tests: padding lengths, and every pad byte checked on removal
+
+use aes::{decrypt_padded, AesError, Padding};
+
+static SCHEMES: [Padding; 4] = [Padding::Pkcs7, Padding::AnsiX923, Padding::Iso7816, Padding::Iso10126];
+
+fn bad(padding: Padding, bytes: &[u8]) -> bool {
+    matches!(padding.unpad(bytes), Err(AesError::BadPadding))
+}
+
+#[test]
+fn round_trip_every_length() {
+    for padding in SCHEMES {
+        for len in 0..=48 {
+            let data: Vec<u8> = (0..len as u8).map(|b| b.wrapping_mul(37)).collect();
+            let padded = padding.pad(&data).unwrap();
+
+            assert!(padded.len().is_multiple_of(16));
+            assert!(padded.len() > data.len() && padded.len() <= data.len() + 16);
+            assert_eq!(padding.unpad(&padded).unwrap(), data, "{:?} length {}", padding, len);
+        }
+    }
+}
+
+#[test]
+fn aligned_input_gets_a_full_block() {
+    let data = [0x41u8; 16];
+
+    let mut pkcs7 = data.to_vec();
+    pkcs7.extend([16u8; 16]);
+    assert_eq!(Padding::Pkcs7.pad(&data).unwrap(), pkcs7);
+
+    let mut x923 = data.to_vec();
+    x923.extend([0u8; 15]);
+    x923.push(16);
+    assert_eq!(Padding::AnsiX923.pad(&data).unwrap(), x923);
+
+    let mut iso7816 = data.to_vec();
+    iso7816.push(0x80);
+    iso7816.extend([0u8; 15]);
+    assert_eq!(Padding::Iso7816.pad(&data).unwrap(), iso7816);
+
+    let iso10126 = Padding::Iso10126.pad(&data).unwrap();
+    assert_eq!(iso10126.len(), 32);
+    assert_eq!(iso10126[31], 16);
+}
+
+#[test]
+fn short_input_padding_bytes() {
+    let data = b"0123456789a";
+
+    assert_eq!(&Padding::Pkcs7.pad(data).unwrap()[11..], [5, 5, 5, 5, 5]);
+    assert_eq!(&Padding::AnsiX923.pad(data).unwrap()[11..], [0, 0, 0, 0, 5]);
+    assert_eq!(&Padding::Iso7816.pad(data).unwrap()[11..], [0x80, 0, 0, 0, 0]);
+    assert_eq!(Padding::Iso10126.pad(data).unwrap()[15], 5);
+}
+
+#[test]
+fn corrupted_pad_byte_in_the_middle() {
+    let mut pkcs7 = Padding::Pkcs7.pad(b"0123456789a").unwrap();
+    pkcs7[13] = 7;
+    assert!(bad(Padding::Pkcs7, &pkcs7));
+
+    let mut x923 = Padding::AnsiX923.pad(b"0123456789a").unwrap();
+    x923[13] = 1;
+    assert!(bad(Padding::AnsiX923, &x923));
+
+    let mut iso7816 = Padding::Iso7816.pad(b"0123456789a").unwrap();
+    iso7816[13] = 1;
+    assert!(bad(Padding::Iso7816, &iso7816));
+
+    // ISO 10126 filler is random, only the length byte can be checked
+    let mut iso10126 = Padding::Iso10126.pad(b"0123456789a").unwrap();
+    iso10126[13] ^= 0xff;
+    assert_eq!(Padding::Iso10126.unpad(&iso10126).unwrap(), b"0123456789a");
+}
+
+#[test]
+fn length_byte_out_of_range() {
+    for padding in [Padding::Pkcs7, Padding::AnsiX923, Padding::Iso10126] {
+        let mut block = [0u8; 16];
+        assert!(bad(padding, &block), "{:?} last == 0", padding);
+
+        block[15] = 17;
+        assert!(bad(padding, &block), "{:?} last == 17", padding);
+
+        block[15] = 0xff;
+        assert!(bad(padding, &block), "{:?} last == 255", padding);
+    }
+}
+
+#[test]
+fn iso7816_needs_the_marker() {
+    assert!(bad(Padding::Iso7816, &[0u8; 16]));
+    assert!(bad(Padding::Iso7816, &[0u8; 32]));
+
+    // the marker must be in the last block
+    let mut marker_too_early = [0u8; 32];
+    marker_too_early[15] = 0x80;
+    assert!(bad(Padding::Iso7816, &marker_too_early));
+
+    // a marker as the very first byte of the last block is fine
+    let mut full_block = [0x41u8; 32];
+    full_block[16] = 0x80;
+    full_block[17..].fill(0);
+    assert_eq!(Padding::Iso7816.unpad(&full_block).unwrap(), [0x41; 16]);
+}
+
+#[test]
+fn unpad_needs_whole_blocks() {
+    for padding in SCHEMES {
+        assert!(bad(padding, &[]));
+        assert!(bad(padding, &[1u8; 15]));
+        assert!(bad(padding, &[1u8; 17]));
+    }
+}
+
+#[test]
+fn no_padding_lengths() {
+    assert!(Padding::NoPadding.pad(&[]).unwrap().is_empty());
+    assert_eq!(Padding::NoPadding.pad(&[7; 32]).unwrap(), [7; 32]);
+    assert!(matches!(Padding::NoPadding.pad(&[7; 15]), Err(AesError::InvalidPlaintextLength(15))));
+    assert!(matches!(Padding::NoPadding.pad(&[7; 17]), Err(AesError::InvalidPlaintextLength(17))));
+
+    // nothing is stripped, and CBC rejects partial blocks before unpadding
+    assert_eq!(Padding::NoPadding.unpad(&[0; 16]).unwrap(), [0; 16]);
+    let key = [0u8; 16];
+    assert!(matches!(decrypt_padded(&[0; 20], &key, [0; 16], Padding::NoPadding), Err(AesError::InvalidCiphertextLength(20))));
+}
+
+#[test]
+fn names() {
+    assert_eq!(Padding::from_name("pkcs7"), Some(Padding::Pkcs7));
+    assert_eq!(Padding::from_name("x923"), Some(Padding::AnsiX923));
+    assert_eq!(Padding::from_name("iso7816"), Some(Padding::Iso7816));
+    assert_eq!(Padding::from_name("iso10126"), Some(Padding::Iso10126));
+    assert_eq!(Padding::from_name("none"), Some(Padding::NoPadding));
+    assert_eq!(Padding::from_name("PKCS7"), None);
+    assert_eq!(Padding::default(), Padding::Pkcs7);
+}
@@ -0,0 +1,34 @@
+// PBKDF2-HMAC-SHA256 tests, the first two are the RFC 7914 section 11
+// vectors and the rest the RFC 6070 inputs with SHA-256 as the PRF
+
+mod common;
+
+use aes::{pbkdf2_hmac_sha256, AesError};
+use common::hex;
+
+// password, salt, iterations, derived key
+static VECTORS: [(&[u8], &[u8], u32, &str); 5] = [
+    (b"passwd", b"salt", 1,
+     "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"),
+    (b"Password", b"NaCl", 80000,
+     "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d"),
+    (b"password", b"salt", 4096,
+     "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"),
+    (b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096,
+     "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"),
+    (b"pass\0word", b"sa\0lt", 4096,
+     "89b69d0516f829893c696226650a8687"),
+];
+
+#[test]
+fn vectors() {
+    for (password, salt, iterations, derived) in VECTORS {
+        let expected = hex(derived);
+        assert_eq!(pbkdf2_hmac_sha256(password, salt, iterations, expected.len()).unwrap(), expected);
+    }
+}
+
+#[test]
+fn rejects_zero_iterations() {
+    assert!(matches!(pbkdf2_hmac_sha256(b"password", b"salt", 0, 32), Err(AesError::InvalidIterationCount(0))));
+}
@@ -0,0 +1,35 @@
+// SHA-256 tests with the FIPS 180-2 Appendix B examples
+
+mod common;
+
+use aes::sha256::sha256;
+use aes::Sha256;
+use common::hex;
+
+static VECTORS: [(&str, &str); 4] = [
+    ("", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+    ("abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+    ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+    ("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+     "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"),
+];
+
+#[test]
+fn fips_examples() {
+    for (message, digest) in VECTORS {
+        assert_eq!(sha256(message.as_bytes()).to_vec(), hex(digest), "{:?}", message);
+    }
+}
+
+#[test]
+fn million_a() {
+    // streamed in uneven pieces
+    let mut hasher = Sha256::new();
+    let chunk = [b'a'; 999];
+    for _ in 0..1001 {
+        hasher.update(&chunk);
+    }
+    hasher.update(&[b'a'; 1]);
+
+    assert_eq!(hasher.finalize().to_vec(), hex("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"));
+}
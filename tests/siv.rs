@@ -0,0 +1,71 @@
+// AES-SIV tests with the RFC 5297 Appendix A vectors
+
+mod common;
+
+use aes::{determine_siv_key_length, AesError, Siv};
+use common::hex;
+
+#[test]
+fn deterministic_example() {
+    // A.1
+    let siv = Siv::new(&hex("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0 f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff")).unwrap();
+    let ad = hex("101112131415161718191a1b1c1d1e1f2021222324252627");
+    let plaintext = hex("112233445566778899aabbccddee");
+    let output = hex("85632d07c6e8f37f950acd320a2ecc93 40c02b9690c4dc04daef7f6afe5c");
+
+    assert_eq!(siv.encrypt(&[&ad], &plaintext).unwrap(), output);
+    assert_eq!(siv.decrypt(&[&ad], &output).unwrap(), plaintext);
+}
+
+#[test]
+fn nonce_based_example() {
+    // A.2, the nonce is the last associated data component
+    let siv = Siv::new(&hex("7f7e7d7c7b7a79787776757473727170 404142434445464748494a4b4c4d4e4f")).unwrap();
+    let ad1 = hex("00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100");
+    let ad2 = hex("102030405060708090a0");
+    let nonce = hex("09f911029d74e35bd84156c5635688c0");
+    let plaintext = hex("7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553");
+    let output = hex("7bdb6e3b432667eb06f4d14bff2fbd0f cb900f2fddbe404326601965c889bf17 dba77ceb094fa663b7a3f748ba8af829
+                      ea64ad544a272e9c485b62a3fd5c0d");
+
+    assert_eq!(siv.encrypt(&[&ad1, &ad2, &nonce], &plaintext).unwrap(), output);
+    assert_eq!(siv.decrypt(&[&ad1, &ad2, &nonce], &output).unwrap(), plaintext);
+
+    // components are not interchangeable
+    assert!(matches!(siv.decrypt(&[&ad2, &ad1, &nonce], &output), Err(AesError::AuthenticationFailed)));
+    assert!(matches!(siv.decrypt(&[&ad1, &ad2], &output), Err(AesError::AuthenticationFailed)));
+}
+
+#[test]
+fn longer_keys() {
+    let key: Vec<u8> = (0..64).collect();
+
+    let siv = Siv::new(&key[..48]).unwrap();
+    let sealed = siv.encrypt(&[b"header"], b"deterministic").unwrap();
+    assert_eq!(sealed, hex("21f0eb57f76d33aa66fe9a9e0efd15598547dc2089b2b723dd54570188"));
+
+    let siv = Siv::new(&key).unwrap();
+    let sealed = siv.encrypt(&[b"header", b""], b"deterministic").unwrap();
+    assert_eq!(sealed, hex("43fdecb5df9227f1f535909f39690818ce2bbe2abdc04889e8afffc9fb"));
+    assert_eq!(siv.decrypt(&[b"header", b""], &sealed).unwrap(), b"deterministic");
+}
+
+#[test]
+fn key_lengths() {
+    assert_eq!(determine_siv_key_length(&[0; 32]).unwrap(), (4, 10));
+    assert_eq!(determine_siv_key_length(&[0; 48]).unwrap(), (6, 12));
+    assert_eq!(determine_siv_key_length(&[0; 64]).unwrap(), (8, 14));
+
+    assert!(matches!(Siv::new(&[0; 16]), Err(AesError::InvalidKeyLength(16))));
+    assert!(matches!(Siv::new(&[0; 40]), Err(AesError::InvalidKeyLength(40))));
+}
+
+#[test]
+fn rejects_tampering() {
+    let siv = Siv::new(&[1; 32]).unwrap();
+    let mut sealed = siv.encrypt(&[], b"some plaintext").unwrap();
+
+    sealed[20] ^= 4;
+    assert!(matches!(siv.decrypt(&[], &sealed), Err(AesError::AuthenticationFailed)));
+    assert!(matches!(siv.decrypt(&[], &sealed[..15]), Err(AesError::InvalidCiphertextLength(15))));
+}